@@ -0,0 +1,235 @@
+use crate::{read_input, Format, Input, Mode, Result};
+use std::env::args;
+
+/// Usage of the day binaries without options of their own.
+const USAGE: &str = "usage: day<n> [<input>|-] [--format <human|json>] [--strict]";
+
+/// Appended to the usage of every day binary.
+const USAGE_FOOTER: &str = "\
+<input> is a file, or stdin for -. Without it the day reads whatever is piped
+in, and only otherwise its file in the inputs directory of the workspace.
+
+--format json prints the answers of the two parts only, so it takes none of
+the options changing what the day prints.";
+
+/// An option a day binary takes besides `--format` and `--strict`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Opt {
+    /// `-k` or `--name`, or `<name>` for a positional argument after the input.
+    pub name: &'static str,
+    pub takes_value: bool,
+    /// Whether `--format json` rejects the option, which it doesn't print.
+    pub human_only: bool,
+}
+
+impl Opt {
+    pub const fn flag(name: &'static str) -> Opt {
+        Opt {
+            name,
+            takes_value: false,
+            human_only: true,
+        }
+    }
+
+    pub const fn value(name: &'static str) -> Opt {
+        Opt {
+            name,
+            takes_value: true,
+            human_only: true,
+        }
+    }
+
+    /// The option also goes with `--format json`, like one that only changes
+    /// how the answers are found.
+    pub const fn any_format(self) -> Opt {
+        Opt {
+            human_only: false,
+            ..self
+        }
+    }
+
+    fn is_positional(&self) -> bool {
+        self.name.starts_with('<')
+    }
+}
+
+/// Command line shared by the day binaries: the input, the options the day
+/// declares plus `--format <human|json>` and `--strict`.
+///
+/// See [`read_input`] for where the input comes from when there is none.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cli {
+    pub format: Format,
+    pub mode: Mode,
+    /// A file, or `-` for stdin.
+    pub input: Option<String>,
+    /// The options given in order, with their values.
+    pub options: Vec<(&'static str, Option<String>)>,
+}
+
+impl Cli {
+    /// Parses `args` taking the options of `opts`, described by `usage` when
+    /// they don't.
+    pub fn parse(mut args: impl Iterator<Item = String>, usage: &str, opts: &[Opt]) -> Result<Cli> {
+        let usage_error = |msg: String| format!("{}\n{}\n\n{}", msg, usage, USAGE_FOOTER);
+        let mut positionals = opts.iter().filter(|opt| opt.is_positional());
+        let mut cli = Cli {
+            format: Format::Human,
+            mode: Mode::Lenient,
+            input: None,
+            options: Vec::new(),
+        };
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+            match arg.as_str() {
+                "--format" => cli.format = value()?.parse()?,
+                "--strict" => cli.mode = Mode::Strict,
+                _ => match opts
+                    .iter()
+                    .find(|opt| opt.name == arg && !opt.is_positional())
+                {
+                    Some(opt) => {
+                        let value = if opt.takes_value {
+                            Some(value()?)
+                        } else {
+                            None
+                        };
+                        cli.options.push((opt.name, value));
+                    }
+                    // A bare `-` is stdin and `-1` a negative number.
+                    None if arg.len() > 1
+                        && arg.starts_with('-')
+                        && !arg[1..].starts_with(|c: char| c.is_ascii_digit()) =>
+                    {
+                        return Err(usage_error(format!("Unknown option: {}", arg)).into());
+                    }
+                    None if cli.input.is_none() => cli.input = Some(arg),
+                    None => match positionals.next() {
+                        Some(opt) => cli.options.push((opt.name, Some(arg))),
+                        None => {
+                            return Err(usage_error(format!("Unexpected argument: {}", arg)).into())
+                        }
+                    },
+                },
+            }
+        }
+        if cli.format == Format::Json {
+            let human_only = |name| opts.iter().any(|opt| opt.name == name && opt.human_only);
+            if let Some((name, _)) = cli.options.iter().find(|(name, _)| human_only(*name)) {
+                return Err(format!("{} can't be combined with --format json", name).into());
+            }
+        }
+        Ok(cli)
+    }
+
+    /// Parses the command line of a day binary without options of its own.
+    pub fn from_env() -> Result<Cli> {
+        Cli::from_env_with(USAGE, &[])
+    }
+
+    /// Parses the command line of a day binary taking `opts`.
+    pub fn from_env_with(usage: &str, opts: &[Opt]) -> Result<Cli> {
+        Cli::parse(args().skip(1), usage, opts)
+    }
+
+    /// Whether the flag `name` is given.
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(option, _)| *option == name)
+    }
+
+    /// The value of the last `name` given.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(option, _)| *option == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// The values of every `name` given, in order.
+    pub fn values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.options
+            .iter()
+            .filter(move |(option, _)| *option == name)
+            .filter_map(|(_, value)| value.as_deref())
+    }
+
+    /// Reads input for `day` as given by the first positional argument.
+    pub fn input(&self, day: u8) -> Result<Input> {
+        read_input(self.input.as_deref(), day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPTS: &[Opt] = &[
+        Opt::value("<target>"),
+        Opt::value("-k"),
+        Opt::flag("--all"),
+        Opt::flag("--hashed").any_format(),
+    ];
+
+    fn parse(args: &[&str]) -> Result<Cli> {
+        Cli::parse(args.iter().map(|s| s.to_string()), USAGE, OPTS)
+    }
+
+    #[test]
+    fn positional_and_format() {
+        let err = parse(&["input.txt", "2020", "--format", "json"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "<target> can't be combined with --format json"
+        );
+
+        let cli = parse(&["--format", "json", "input.txt", "--hashed"]).unwrap();
+        assert_eq!(cli.format, Format::Json);
+        assert_eq!(cli.input.as_deref(), Some("input.txt"));
+        assert!(cli.flag("--hashed"));
+
+        let cli = parse(&["input.txt", "2020"]).unwrap();
+        assert_eq!(cli.format, Format::Human);
+        assert_eq!(cli.mode, Mode::Lenient);
+        assert_eq!(cli.value("<target>"), Some("2020"));
+
+        let cli = parse(&["input.txt", "--strict"]).unwrap();
+        assert_eq!(cli.mode, Mode::Strict);
+        assert_eq!(cli.options, vec![]);
+    }
+
+    #[test]
+    fn stdin_and_negative_numbers() {
+        let cli = parse(&["-", "2020"]).unwrap();
+        assert_eq!(cli.input.as_deref(), Some("-"));
+        assert_eq!(cli.value("<target>"), Some("2020"));
+
+        let cli = parse(&["-", "-5", "-k", "-1"]).unwrap();
+        assert_eq!(cli.value("<target>"), Some("-5"));
+        assert_eq!(cli.value("-k"), Some("-1"));
+    }
+
+    #[test]
+    fn options() {
+        let cli = parse(&["-k", "3", "--all", "-k", "4"]).unwrap();
+        assert_eq!(cli.input, None);
+        assert!(cli.flag("--all"));
+        assert!(!cli.flag("--hashed"));
+        assert_eq!(cli.value("-k"), Some("4"));
+        assert_eq!(cli.values("-k").collect::<Vec<_>>(), vec!["3", "4"]);
+
+        let err = parse(&["--merge"]).unwrap_err().to_string();
+        assert!(err.starts_with("Unknown option: --merge\nusage: "));
+        let err = parse(&["input.txt", "2020", "2021"])
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Unexpected argument: 2021\nusage: "));
+        assert!(parse(&["-k"]).is_err());
+    }
+
+    #[test]
+    fn invalid_format() {
+        assert!(parse(&["--format", "xml"]).is_err());
+        assert!(parse(&["input.txt", "--format"]).is_err());
+    }
+}
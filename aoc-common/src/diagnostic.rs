@@ -0,0 +1,161 @@
+use std::{fmt, str::FromStr};
+
+/// How malformed lines are treated by parsers which used to skip them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Mode {
+    /// Malformed lines are skipped.
+    Lenient,
+    /// The first malformed line is an error.
+    Strict,
+}
+
+/// Parse error pointing at the offending place of the input.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    pub file: Option<String>,
+    /// 1-based line number
+    pub line: usize,
+    /// 1-based column, counted in chars
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}", file, self.line, self.column)?,
+            None => write!(f, "line {}, column {}", self.line, self.column)?,
+        }
+        if self.found.is_empty() {
+            write!(f, ": expected {}, found end of line", self.expected)
+        } else {
+            write!(f, ": expected {}, found {:?}", self.expected, self.found)
+        }
+    }
+}
+
+/// Whole puzzle input, used to locate subslices of it in diagnostics.
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'a> {
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Source { input }
+    }
+
+    fn offset(&self, at: &str) -> usize {
+        let start = self.input.as_ptr() as usize;
+        (at.as_ptr() as usize)
+            .saturating_sub(start)
+            .min(self.input.len())
+    }
+
+    /// Line and column of `at`, which has to be a subslice of the input.
+    pub fn position(&self, at: &str) -> (usize, usize) {
+        let before = &self.input[..self.offset(at)];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        let line = before.matches('\n').count() + 1;
+        (line, before[line_start..].chars().count() + 1)
+    }
+
+    /// Creates a diagnostic pointing at `at`, which has to be a subslice of the input.
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> Diagnostic {
+        let (line, column) = self.position(at);
+        let rest = &self.input[self.offset(at)..];
+        Diagnostic {
+            file: None,
+            line,
+            column,
+            expected: expected.into(),
+            found: rest[..rest.find('\n').unwrap_or(rest.len())].to_owned(),
+        }
+    }
+
+    /// Splits `s` on the first `sep`, pointing at the end of `s` when it is missing.
+    pub fn split(&self, s: &'a str, sep: &str) -> Result<(&'a str, &'a str), Diagnostic> {
+        match s.find(sep) {
+            Some(idx) => Ok((&s[..idx], &s[idx + sep.len()..])),
+            None => Err(self.error(&s[s.len()..], format!("{:?}", sep))),
+        }
+    }
+
+    /// Parses the whole of `s`, reporting `expected` on failure.
+    pub fn parse<T: FromStr>(&self, s: &str, expected: &str) -> Result<T, Diagnostic> {
+        s.parse().map_err(|_| self.error(s, expected))
+    }
+}
+
+/// Collects parsed items, skipping malformed ones unless `mode` is strict.
+pub fn collect<T>(
+    items: impl Iterator<Item = Result<T, Diagnostic>>,
+    mode: Mode,
+) -> Result<Vec<T>, Diagnostic> {
+    match mode {
+        Mode::Strict => items.collect(),
+        Mode::Lenient => Ok(items.filter_map(Result::ok).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "nop +0\nacc +1\r\njmp -2\n";
+
+    #[test]
+    fn locate_token() {
+        let source = Source::new(INPUT);
+        let line = INPUT.lines().nth(2).unwrap();
+        let d = source.error(&line[4..], "number");
+        assert_eq!((d.line, d.column), (3, 5));
+        assert_eq!(d.found, "-2");
+        assert_eq!(
+            d.to_string(),
+            "line 3, column 5: expected number, found \"-2\""
+        );
+    }
+
+    #[test]
+    fn carriage_return() {
+        let input = "acc +1\r\r\n";
+        let source = Source::new(input);
+        let line = input.lines().next().unwrap();
+        let err = source.parse::<i32>(&line[4..], "number").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+        assert!(err.to_string().ends_with("found \"+1\\r\\r\""));
+    }
+
+    #[test]
+    fn missing_separator() {
+        let source = Source::new(INPUT);
+        let line = INPUT.lines().next().unwrap();
+        assert_eq!(source.split(line, " "), Ok(("nop", "+0")));
+        let err = source.split(line, ":").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 7));
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 7: expected \":\", found end of line"
+        );
+    }
+
+    #[test]
+    fn strict_collect() {
+        let source = Source::new(INPUT);
+        let parse = || {
+            INPUT
+                .lines()
+                .map(|l| source.parse::<i32>(l, "number").or(Ok(0)))
+        };
+        assert_eq!(collect(parse(), Mode::Strict).unwrap(), vec![0, 0, 0]);
+
+        let items = vec![Ok(1), Err(source.error(INPUT, "x")), Ok(2)];
+        assert_eq!(
+            collect(items.clone().into_iter(), Mode::Lenient).unwrap(),
+            vec![1, 2]
+        );
+        assert!(collect(items.into_iter(), Mode::Strict).is_err());
+    }
+}
@@ -0,0 +1,317 @@
+use crate::{Diagnostic, Mode, Source};
+use std::{
+    fmt,
+    iter::successors,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+/// Offsets of the orthogonal neighbors.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the orthogonal and diagonal neighbors.
+pub const SURROUNDING: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Rectangular grid stored row by row, indexed by `(x, y)` where `x` is the column.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "grid size doesn't match its cells"
+        );
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one row per line, mapping every char with `tile`.
+    ///
+    /// `expected` describes the chars `tile` accepts, every row has to be as
+    /// long as the first one. Other chars are errors in strict mode and
+    /// default cells otherwise.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mode: Mode,
+        mut tile: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, Diagnostic>
+    where
+        T: Default,
+    {
+        let source = Source::new(input);
+        let (mut cells, mut width, mut height) = (Vec::new(), None, 0);
+        for line in input.trim().lines() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            for (idx, c) in line.char_indices() {
+                let cell = match (tile(c), mode) {
+                    (Some(cell), _) => cell,
+                    (None, Mode::Lenient) => T::default(),
+                    (None, Mode::Strict) => return Err(source.error(&line[idx..], expected)),
+                };
+                cells.push(cell);
+            }
+            match width {
+                None => width = Some(line.chars().count()),
+                Some(width) if width != line.chars().count() => {
+                    let end = line.char_indices().nth(width).map_or(line.len(), |c| c.0);
+                    let expected = format!("{} tiles per row", width);
+                    return Err(source.error(&line[end..], expected));
+                }
+                _ => (),
+            }
+            height += 1;
+        }
+        Ok(Grid::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Cell at `(x, y)` with both coordinates wrapping around, as if the grid was a torus.
+    pub fn wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    /// Moves `pos` by `offset`, `None` when it leaves the grid.
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = (x as isize).checked_add(dx)?;
+        let y = (y as isize).checked_add(dy)?;
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        Some((x as usize, y as usize))
+    }
+
+    /// Positions reachable from `pos` by `offsets` which are inside the grid.
+    pub fn neighbors<'a>(
+        &'a self,
+        pos: (usize, usize),
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&offset| self.offset(pos, offset))
+    }
+
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(pos, &ORTHOGONAL)
+    }
+
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(pos, &SURROUNDING)
+    }
+
+    /// Positions along the ray from `pos` in direction `dir` up to the edge,
+    /// `pos` itself excluded.
+    pub fn ray(
+        &self,
+        pos: (usize, usize),
+        dir: (isize, isize),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let first = self.offset(pos, dir).filter(|_| dir != (0, 0));
+        successors(first, move |&pos| self.offset(pos, dir))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Draws the grid with one char per cell and a newline after every row.
+    pub fn render(&self, mut tile: impl FnMut(&T) -> char) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            out.extend(row.iter().map(&mut tile));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).expect("position outside of the grid")
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y).expect("position outside of the grid")
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "#..\n.#.\n..#\n#..\n";
+
+    fn parse(input: &str) -> Result<Grid<bool>, Diagnostic> {
+        parse_in(input, Mode::Strict)
+    }
+
+    fn parse_in(input: &str, mode: Mode) -> Result<Grid<bool>, Diagnostic> {
+        Grid::parse(input, "'#' or '.'", mode, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = parse(INPUT).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert!(grid[(0, 0)] && grid[(2, 2)] && !grid[(2, 0)]);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), INPUT);
+        assert_eq!(grid.map(|&b| b as u8).to_string(), "100\n010\n001\n100\n");
+        assert_eq!(grid.iter().filter(|&&b| b).count(), 4);
+
+        let empty = parse("").unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.render(|_| '#'), "");
+    }
+
+    #[test]
+    fn malformed_rows() {
+        let err = parse("#..\n.x.\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = parse("#..\n.#\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 3, "3 tiles per row")
+        );
+        let err = parse("#..\n.#..\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        let err = parse_in("#..\n.#\n", Mode::Lenient).unwrap_err();
+        assert_eq!(err.expected, "3 tiles per row");
+    }
+
+    #[test]
+    fn unknown_chars_and_line_endings() {
+        let grid = parse_in("#x.\n.#.\n", Mode::Lenient).unwrap();
+        assert_eq!(grid.render(|&b| if b { '#' } else { '.' }), "#..\n.#.\n");
+        assert_eq!(
+            parse("#..\r\n.#.\r\n").unwrap(),
+            parse("#..\n.#.\n").unwrap()
+        );
+        assert_eq!(parse(".#\r\n#.\r").unwrap(), parse(".#\n#.").unwrap());
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = parse(INPUT).unwrap();
+        let corner: Vec<_> = grid.neighbors8((0, 0)).collect();
+        assert_eq!(corner, vec![(1, 0), (0, 1), (1, 1)]);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbors4((2, 3)).collect::<Vec<_>>(),
+            vec![(2, 2), (1, 3)]
+        );
+    }
+
+    #[test]
+    fn rays() {
+        let grid = parse(INPUT).unwrap();
+        let diagonal: Vec<_> = grid.ray((0, 0), (1, 1)).collect();
+        assert_eq!(diagonal, vec![(1, 1), (2, 2)]);
+        assert_eq!(grid.ray((0, 0), (-1, 0)).count(), 0);
+        assert_eq!(grid.ray((0, 0), (0, 0)).count(), 0);
+        let first = grid.ray((0, 1), (0, 1)).find(|&pos| grid[pos]);
+        assert_eq!(first, Some((0, 3)));
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid = parse(INPUT).unwrap();
+        assert!(*grid.wrapping(3, 4));
+        assert!(*grid.wrapping(-1, -2));
+        assert!(!*grid.wrapping(7, 0));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{Error, Mode, Result, Solution};
+use std::{
+    env::current_dir,
+    fs::read_to_string,
+    io::{stdin, IsTerminal, Read},
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+pub const INPUTS_DIR: &str = "inputs";
+
+const STDIN: &str = "<stdin>";
+
+/// Puzzle input together with the name diagnostics refer to it by.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Input {
+    /// File path, or `<stdin>`
+    pub name: String,
+    pub text: String,
+}
+
+impl Input {
+    pub fn new(name: impl Into<String>, text: String) -> Self {
+        Input {
+            name: name.into(),
+            text,
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text =
+            read_to_string(path).map_err(|e| Error::Parse(format!("{}: {}", path.display(), e)))?;
+        Ok(Input::new(path.display().to_string(), text))
+    }
+
+    /// Parses the input with `S`, pointing diagnostics at this input.
+    pub fn parse<S: Solution>(&self, mode: Mode) -> Result<S::Input> {
+        S::parse(&self.text, mode).map_err(|e| e.in_file(&self.name))
+    }
+}
+
+impl Deref for Input {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
+
+pub fn input_file_name(day: u8) -> String {
+    format!("day{:02}.txt", day)
+}
+
+fn is_workspace_root(dir: &Path) -> bool {
+    read_to_string(dir.join("Cargo.toml"))
+        .map(|manifest| manifest.contains("[workspace]"))
+        .unwrap_or(false)
+}
+
+/// Workspace roots to look for inputs in: the one containing the current
+/// directory, then the one this crate was built from.
+pub fn workspace_roots() -> Vec<PathBuf> {
+    let mut roots = Vec::new();
+    if let Ok(cwd) = current_dir() {
+        if let Some(root) = cwd.ancestors().find(|dir| is_workspace_root(dir)) {
+            roots.push(root.to_path_buf());
+        }
+    }
+    if let Some(root) = Path::new(env!("CARGO_MANIFEST_DIR")).parent() {
+        if !roots.iter().any(|r| r == root) {
+            roots.push(root.to_path_buf());
+        }
+    }
+    roots
+}
+
+/// Every location `inputs/dayNN.txt` is looked up in, in order.
+pub fn input_candidates(day: u8) -> Vec<PathBuf> {
+    workspace_roots()
+        .into_iter()
+        .map(|root| root.join(INPUTS_DIR).join(input_file_name(day)))
+        .collect()
+}
+
+pub fn find_input(day: u8) -> Result<PathBuf> {
+    let candidates = input_candidates(day);
+    match candidates.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(Error::InputNotFound(
+            candidates.iter().map(|p| p.display().to_string()).collect(),
+        )),
+    }
+}
+
+pub fn read_stdin() -> Result<Input> {
+    let mut input = String::new();
+    stdin().read_to_string(&mut input)?;
+    Ok(Input::new(STDIN, input))
+}
+
+/// Reads puzzle input for `day`.
+///
+/// `-` reads stdin, any other argument is a file path. Without an argument
+/// whatever is piped in wins, and only when stdin is a terminal or empty is
+/// the input looked up in the workspace `inputs` directory.
+pub fn read_input(arg: Option<&str>, day: u8) -> Result<Input> {
+    match arg {
+        Some("-") => read_stdin(),
+        Some(path) => Input::from_file(Path::new(path)),
+        None => {
+            if !stdin().is_terminal() {
+                let input = read_stdin()?;
+                if !input.is_empty() {
+                    return Ok(input);
+                }
+            }
+            match find_input(day) {
+                Ok(path) => Input::from_file(&path),
+                Err(Error::InputNotFound(mut searched)) => {
+                    searched.push("stdin".to_owned());
+                    Err(Error::InputNotFound(searched))
+                }
+                Err(e) => Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_names() {
+        assert_eq!(input_file_name(3), "day03.txt");
+        assert_eq!(input_file_name(17), "day17.txt");
+    }
+
+    #[test]
+    fn candidates_in_workspace() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        assert!(input_candidates(1).contains(&root.join("inputs").join("day01.txt")));
+        assert!(workspace_roots().iter().all(|root| is_workspace_root(root)));
+    }
+
+    #[test]
+    fn missing_input_lists_locations() {
+        let err = find_input(200).unwrap_err();
+        match &err {
+            Error::InputNotFound(searched) => assert!(!searched.is_empty()),
+            e => panic!("unexpected error {:?}", e),
+        }
+        assert!(err.to_string().contains("day200.txt"));
+    }
+}
@@ -0,0 +1,150 @@
+use std::{fmt, io, num::ParseIntError, str::FromStr};
+
+mod cli;
+mod diagnostic;
+mod grid;
+mod input;
+mod output;
+mod solution;
+
+pub use cli::{Cli, Opt};
+pub use diagnostic::{collect, Diagnostic, Mode, Source};
+pub use grid::{Grid, ORTHOGONAL, SURROUNDING};
+pub use input::{find_input, input_file_name, read_input, workspace_roots, Input, INPUTS_DIR};
+pub use output::{json_string, print_json, solve_timed, Format, Record};
+pub use solution::{Part, Solution};
+
+pub enum Error {
+    InputNotFound(Vec<String>),
+    NoSolution,
+    Io(io::Error),
+    Parse(String),
+    Diagnostic(Diagnostic),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InputNotFound(searched) => write!(
+                f,
+                "No input file given and none found, searched: {}",
+                searched.join(", ")
+            ),
+            Error::NoSolution => write!(f, "No solution found"),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::Parse(msg) => write!(f, "{}", msg),
+            Error::Diagnostic(d) => write!(f, "{}", d),
+        }
+    }
+}
+
+// `main` reports errors through `Debug`, keep it readable
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Error {
+    /// Attributes a diagnostic to `file`, unless it already names one.
+    pub fn in_file(self, file: &str) -> Self {
+        match self {
+            Error::Diagnostic(mut d) => {
+                d.file.get_or_insert_with(|| file.to_owned());
+                Error::Diagnostic(d)
+            }
+            e => e,
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Error::Parse(e.to_string())
+    }
+}
+
+impl From<Diagnostic> for Error {
+    fn from(d: Diagnostic) -> Self {
+        Error::Diagnostic(d)
+    }
+}
+
+impl From<String> for Error {
+    fn from(msg: String) -> Self {
+        Error::Parse(msg)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(msg: &str) -> Self {
+        Error::Parse(msg.to_owned())
+    }
+}
+
+/// Splits `input` on the first occurrence of `sep`.
+pub fn split_at_once<'a>(input: &'a str, sep: &str) -> Option<(&'a str, &'a str)> {
+    let mut s = input.splitn(2, sep);
+    s.next().and_then(|n| s.next().map(|v| (n, v)))
+}
+
+/// Iterates over blocks of input separated by an empty line.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input.trim().split("\n\n")
+}
+
+/// Parses every line of input, failing on the first line that does not parse.
+pub fn parse_lines<T: FromStr>(input: &str) -> Result<Vec<T>, T::Err> {
+    input.lines().map(str::parse).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_on_first_separator() {
+        assert_eq!(split_at_once("1-3 a: abc", "-"), Some(("1", "3 a: abc")));
+        assert_eq!(
+            split_at_once("a contain b contain c", " contain "),
+            Some(("a", "b contain c"))
+        );
+        assert_eq!(split_at_once("abc", ":"), None);
+    }
+
+    #[test]
+    fn blank_line_sections() {
+        let blocks: Vec<_> = sections("a\nb\n\nc\n\nd\n").collect();
+        assert_eq!(blocks, vec!["a\nb", "c", "d"]);
+    }
+
+    #[test]
+    fn parse_every_line() {
+        assert_eq!(parse_lines::<i32>("1\n-2\n3\n").unwrap(), vec![1, -2, 3]);
+        assert!(parse_lines::<i32>("1\nx\n").is_err());
+    }
+
+    #[test]
+    fn parse_part() {
+        assert_eq!("1".parse::<Part>().unwrap(), Part::One);
+        assert_eq!("2".parse::<Part>().unwrap(), Part::Two);
+        assert!("3".parse::<Part>().is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{Error, Input, Mode, Part, Result, Solution};
+use std::{
+    fmt::Write,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Human,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Invalid format: {}", s).into()),
+        }
+    }
+}
+
+/// Answer of a single part, printed as one JSON object per line.
+pub struct Record<'a> {
+    pub day: u8,
+    pub part: Part,
+    pub answer: &'a Result<String>,
+    pub elapsed: Duration,
+}
+
+impl Record<'_> {
+    pub fn to_json(&self) -> String {
+        let mut out = format!("{{\"day\":{},\"part\":{},", self.day, self.part.number());
+        match self.answer {
+            Ok(answer) => write!(out, "\"answer\":{},", json_string(answer)),
+            Err(e) => write!(out, "\"error\":{},", json_string(&e.to_string())),
+        }
+        .unwrap();
+        write!(
+            out,
+            "\"elapsed_ms\":{:.3}}}",
+            self.elapsed.as_secs_f64() * 1000.0
+        )
+        .unwrap();
+        out
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Solves a single part, timing both parsing and solving.
+pub fn solve_timed(
+    solve: fn(&str, Part, Mode) -> Result<String>,
+    input: &Input,
+    part: Part,
+    mode: Mode,
+) -> (Result<String>, Duration) {
+    let start = Instant::now();
+    let answer = solve(input, part, mode).map_err(|e| e.in_file(&input.name));
+    (answer, start.elapsed())
+}
+
+/// Prints both parts of `S` as JSON records.
+pub fn print_json<S: Solution>(day: u8, input: &Input, mode: Mode) {
+    for &part in Part::ALL.iter() {
+        let (answer, elapsed) = solve_timed(S::solve, input, part, mode);
+        let record = Record {
+            day,
+            part,
+            answer: &answer,
+            elapsed,
+        };
+        println!("{}", record.to_json());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape() {
+        assert_eq!(json_string("123"), "\"123\"");
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+
+    #[test]
+    fn records() {
+        let answer = Ok("514579".to_owned());
+        let record = Record {
+            day: 1,
+            part: Part::One,
+            answer: &answer,
+            elapsed: Duration::from_micros(1500),
+        };
+        assert_eq!(
+            record.to_json(),
+            "{\"day\":1,\"part\":1,\"answer\":\"514579\",\"elapsed_ms\":1.500}"
+        );
+
+        let answer = Err(Error::NoSolution);
+        let record = Record {
+            day: 9,
+            part: Part::Two,
+            answer: &answer,
+            elapsed: Duration::from_millis(2),
+        };
+        assert_eq!(
+            record.to_json(),
+            "{\"day\":9,\"part\":2,\"error\":\"No solution found\",\"elapsed_ms\":2.000}"
+        );
+    }
+}
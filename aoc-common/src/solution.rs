@@ -0,0 +1,59 @@
+use crate::{Error, Mode, Result};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "part1"),
+            Part::Two => write!(f, "part2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("Invalid part: {}", s).into()),
+        }
+    }
+}
+
+/// Puzzle solver for a single day.
+///
+/// Input is parsed once and then shared by both parts.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input>;
+    fn part1(input: &Self::Input) -> Result<String>;
+    fn part2(input: &Self::Input) -> Result<String>;
+
+    fn solve(input: &str, part: Part, mode: Mode) -> Result<String> {
+        let input = Self::parse(input, mode)?;
+        match part {
+            Part::One => Self::part1(&input),
+            Part::Two => Self::part2(&input),
+        }
+    }
+}
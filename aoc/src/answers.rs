@@ -0,0 +1,127 @@
+use aoc_common::{Part, Result};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs::{read_to_string, write},
+    io::ErrorKind,
+    path::Path,
+};
+
+const HEADER: &str = "\
+# Known answers checked by `aoc verify`, updated by `aoc verify --record`.
+#
+# Puzzle inputs aren't committed, so the rows are recorded from the examples
+# of the puzzles in samples/ and checked by `aoc verify --inputs samples`. For
+# your own inputs, put them in inputs/dayNN.txt and run `aoc verify --record`
+# once adventofcode.com accepted the answers. A row only checks the input it
+# was recorded from, others show up as CHANGED.
+# day\tpart\tinput hash\tanswer
+";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Entry {
+    pub hash: u64,
+    pub answer: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    entries: BTreeMap<(u8, Part), Entry>,
+}
+
+/// FNV-1a hash of the puzzle input, stable across builds and platforms.
+pub fn input_hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+impl Answers {
+    /// Loads answers from `path`, a missing file is treated as empty.
+    pub fn load(path: &Path) -> Result<Answers> {
+        match read_to_string(path) {
+            Ok(content) => Answers::parse(&content),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Answers> {
+        let mut entries = BTreeMap::new();
+        for (idx, line) in content.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(4, '\t');
+            let mut field = || {
+                fields
+                    .next()
+                    .ok_or(format!("Missing field on answers line {}", idx + 1))
+            };
+            let day = field()?.parse()?;
+            let part = field()?.parse()?;
+            let hash = u64::from_str_radix(field()?, 16)?;
+            let answer = field()?.to_owned();
+            entries.insert((day, part), Entry { hash, answer });
+        }
+        Ok(Answers { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut content = HEADER.to_owned();
+        for ((day, part), entry) in &self.entries {
+            writeln!(
+                content,
+                "{}\t{}\t{:016x}\t{}",
+                day,
+                part.number(),
+                entry.hash,
+                entry.answer
+            )
+            .unwrap();
+        }
+        Ok(write(path, content)?)
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Entry> {
+        self.entries.get(&(day, part))
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, entry: Entry) {
+        self.entries.insert((day, part), entry);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(input_hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(input_hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(input_hash("1\n2\n"), input_hash("1\n2"));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut answers = Answers::default();
+        let entry = Entry {
+            hash: input_hash("0,3,6"),
+            answer: "436".to_owned(),
+        };
+        answers.insert(15, Part::One, entry.clone());
+
+        let mut content = HEADER.to_owned();
+        content.push_str(&format!("15\t1\t{:016x}\t436\n", entry.hash));
+        let parsed = Answers::parse(&content).unwrap();
+        assert_eq!(parsed.get(15, Part::One), Some(&entry));
+        assert_eq!(parsed.get(15, Part::Two), None);
+    }
+
+    #[test]
+    fn invalid_line() {
+        assert!(Answers::parse("1\t1\n").is_err());
+        assert!(Answers::parse("1\t3\t00\t5\n").is_err());
+    }
+}
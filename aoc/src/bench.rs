@@ -0,0 +1,125 @@
+use aoc_common::{Mode, Result, Solution};
+use std::{
+    fmt::Write,
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part1",
+            Phase::Part2 => "part2",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Summary {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Summary {
+    pub fn new(mut samples: Vec<Duration>) -> Option<Summary> {
+        samples.sort_unstable();
+        Some(Summary {
+            min: *samples.first()?,
+            median: samples[samples.len() / 2],
+            max: *samples.last()?,
+        })
+    }
+}
+
+/// Time spent in every phase, one sample per iteration.
+#[derive(Debug, Default)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub part1: Vec<Duration>,
+    pub part2: Vec<Duration>,
+}
+
+impl Timings {
+    pub fn samples(&self, phase: Phase) -> &[Duration] {
+        match phase {
+            Phase::Parse => &self.parse,
+            Phase::Part1 => &self.part1,
+            Phase::Part2 => &self.part2,
+        }
+    }
+}
+
+fn timed<T>(samples: &mut Vec<Duration>, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = black_box(f());
+    samples.push(start.elapsed());
+    result
+}
+
+/// Runs parse and both parts `iterations` times, each phase timed separately.
+pub fn bench<S: Solution>(input: &str, mode: Mode, iterations: usize) -> Result<Timings> {
+    let mut timings = Timings::default();
+    for _ in 0..iterations {
+        let parsed = timed(&mut timings.parse, || S::parse(black_box(input), mode))?;
+        timed(&mut timings.part1, || S::part1(&parsed))?;
+        timed(&mut timings.part2, || S::part2(&parsed))?;
+    }
+    Ok(timings)
+}
+
+pub const CSV_HEADER: &str = "day,phase,iterations,min_ns,median_ns,max_ns\n";
+
+pub fn csv_row(out: &mut String, day: u8, phase: Phase, iterations: usize, summary: &Summary) {
+    writeln!(
+        out,
+        "{},{},{},{},{},{}",
+        day,
+        phase.name(),
+        iterations,
+        summary.min.as_nanos(),
+        summary.median.as_nanos(),
+        summary.max.as_nanos()
+    )
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let samples = [5, 1, 3, 2, 4]
+            .iter()
+            .map(|&ms| Duration::from_millis(ms))
+            .collect();
+        let summary = Summary::new(samples).unwrap();
+        assert_eq!(summary.min, Duration::from_millis(1));
+        assert_eq!(summary.median, Duration::from_millis(3));
+        assert_eq!(summary.max, Duration::from_millis(5));
+        assert_eq!(Summary::new(Vec::new()), None);
+    }
+
+    #[test]
+    fn csv() {
+        let summary = Summary {
+            min: Duration::from_nanos(1),
+            median: Duration::from_nanos(20),
+            max: Duration::from_micros(3),
+        };
+        let mut out = String::new();
+        csv_row(&mut out, 7, Phase::Part2, 10, &summary);
+        assert_eq!(out, "7,part2,10,1,20,3000\n");
+    }
+}
@@ -0,0 +1,44 @@
+use crate::bench::{bench, Timings};
+use aoc_common::{Mode, Part, Result, Solution};
+
+pub struct Day {
+    pub day: u8,
+    pub solve: fn(&str, Part, Mode) -> Result<String>,
+    pub bench: fn(&str, Mode, usize) -> Result<Timings>,
+}
+
+impl Day {
+    const fn new<S: Solution>(day: u8) -> Self {
+        Day {
+            day,
+            solve: S::solve,
+            bench: bench::<S>,
+        }
+    }
+}
+
+pub const DAYS: [Day; 19] = [
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day3::Day3>(3),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day6::Day6>(6),
+    Day::new::<day7::Day7>(7),
+    Day::new::<day8::Day8>(8),
+    Day::new::<day9::Day9>(9),
+    Day::new::<day10::Day10>(10),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15::Day15>(15),
+    Day::new::<day16::Day16>(16),
+    Day::new::<day17::Day17>(17),
+    Day::new::<day18::Day18>(18),
+    Day::new::<day19::Day19>(19),
+];
+
+pub fn find(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
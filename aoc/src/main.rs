@@ -0,0 +1,269 @@
+use answers::{input_hash, Answers, Entry};
+use aoc_common::{
+    find_input, input_file_name, read_input, solve_timed, workspace_roots, Format, Input, Mode,
+    Part, Record, Result,
+};
+use bench::{Phase, Summary};
+use std::{env::args, fs::write, path::PathBuf};
+
+mod answers;
+mod bench;
+mod days;
+
+const USAGE: &str = "\
+usage: aoc run --day <day> [--part <part>] [--format <human|json>] [--strict] [<input>|-]
+       aoc run --all [--inputs <dir>] [--format <human|json>] [--strict]
+       aoc verify [--day <day>] [--part <part>] [--inputs <dir>] [--answers <file>] [--record] [--strict]
+       aoc bench [--day <day>] [--iterations <n>] [--inputs <dir>] [--output <file>] [--strict]
+
+--strict turns lines the solvers would otherwise skip into errors.
+
+verify compares every answer with the one in answers.txt recorded for the same
+input, --record saves the new and changed ones; the committed ones are for the
+examples in samples/, see answers.txt for how it's filled in.
+
+run reads <input>, or stdin for -. Without <input> it reads whatever is piped
+in, and only otherwise inputs/dayNN.txt of the workspace.";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Command {
+    Run,
+    Verify,
+    Bench,
+}
+
+struct Args {
+    command: Command,
+    day: Option<u8>,
+    part: Option<Part>,
+    all: bool,
+    record: bool,
+    format: Format,
+    mode: Mode,
+    iterations: usize,
+    inputs: Option<PathBuf>,
+    answers: Option<PathBuf>,
+    output: PathBuf,
+    input: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args> {
+    let command = match args.next().as_deref() {
+        Some("run") => Command::Run,
+        Some("verify") => Command::Verify,
+        Some("bench") => Command::Bench,
+        Some(cmd) => return Err(format!("Unknown command: {}\n{}", cmd, USAGE).into()),
+        None => return Err(USAGE.into()),
+    };
+
+    let mut parsed = Args {
+        command,
+        day: None,
+        part: None,
+        all: false,
+        record: false,
+        format: Format::Human,
+        mode: Mode::Lenient,
+        iterations: 10,
+        inputs: None,
+        answers: None,
+        output: PathBuf::from("bench.csv"),
+        input: None,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--day" => parsed.day = Some(value()?.parse()?),
+            "--part" => parsed.part = Some(value()?.parse()?),
+            "--inputs" => parsed.inputs = Some(PathBuf::from(value()?)),
+            "--answers" => parsed.answers = Some(PathBuf::from(value()?)),
+            "--output" => parsed.output = PathBuf::from(value()?),
+            "--iterations" => parsed.iterations = value()?.parse()?,
+            "--format" => parsed.format = value()?.parse()?,
+            "--all" => parsed.all = true,
+            "--record" => parsed.record = true,
+            "--strict" => parsed.mode = Mode::Strict,
+            _ if arg.starts_with("--") => {
+                return Err(format!("Unknown option: {}\n{}", arg, USAGE).into())
+            }
+            _ => parsed.input = Some(arg),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Reads input of `day` from the `--inputs` directory, or from the workspace
+/// `inputs` directory when none was given.
+fn load_input(args: &Args, day: u8) -> Result<Input> {
+    let path = match &args.inputs {
+        Some(dir) => dir.join(input_file_name(day)),
+        None => find_input(day)?,
+    };
+    Input::from_file(&path)
+}
+
+/// `--answers`, or `answers.txt` in the workspace root.
+fn answers_path(args: &Args) -> PathBuf {
+    match (&args.answers, workspace_roots().first()) {
+        (Some(path), _) => path.clone(),
+        (None, Some(root)) => root.join("answers.txt"),
+        (None, None) => PathBuf::from("answers.txt"),
+    }
+}
+
+/// Days selected by `--day`, or all of them.
+fn selected_days(args: &Args) -> Result<Vec<&'static days::Day>> {
+    match args.day {
+        Some(day) => Ok(vec![days::find(day).ok_or(format!("Unknown day: {}", day))?]),
+        None => Ok(days::DAYS.iter().collect()),
+    }
+}
+
+fn run(day: &days::Day, parts: &[Part], input: &Input, args: &Args) {
+    for &part in parts {
+        let (answer, elapsed) = solve_timed(day.solve, input, part, args.mode);
+        match (args.format, &answer) {
+            (Format::Json, _) => {
+                let record = Record {
+                    day: day.day,
+                    part,
+                    answer: &answer,
+                    elapsed,
+                };
+                println!("{}", record.to_json());
+            }
+            (Format::Human, Ok(answer)) => println!("day{:02} {}: {}", day.day, part, answer),
+            (Format::Human, Err(e)) => println!("day{:02} {}: error: {}", day.day, part, e),
+        }
+    }
+}
+
+/// Compares every answer with the recorded one, returns the number of failures.
+fn verify(args: &Args, parts: &[Part], answers: &mut Answers) -> Result<usize> {
+    let mut failures = 0;
+    for day in selected_days(args)? {
+        let input = match load_input(args, day.day) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("day{:02}: SKIP {}", day.day, e);
+                continue;
+            }
+        };
+        let hash = input_hash(&input);
+
+        for &part in parts {
+            let answer = match (day.solve)(&input, part, args.mode) {
+                Ok(answer) => answer,
+                Err(e) => {
+                    failures += 1;
+                    let e = e.in_file(&input.name);
+                    println!("day{:02} {}: FAIL error: {}", day.day, part, e);
+                    continue;
+                }
+            };
+            let (status, note) = match answers.get(day.day, part) {
+                None => ("NEW", String::new()),
+                Some(entry) if entry.hash != hash => {
+                    ("CHANGED", format!(" (input changed, was {})", entry.answer))
+                }
+                Some(entry) if entry.answer == answer => ("PASS", String::new()),
+                Some(entry) => {
+                    if !args.record {
+                        failures += 1;
+                    }
+                    ("FAIL", format!(" (expected {})", entry.answer))
+                }
+            };
+            let recorded = args.record && status != "PASS";
+            println!(
+                "day{:02} {}: {} {}{}{}",
+                day.day,
+                part,
+                status,
+                answer,
+                note,
+                if recorded { " recorded" } else { "" }
+            );
+            if recorded {
+                answers.insert(day.day, part, Entry { hash, answer });
+            }
+        }
+    }
+    Ok(failures)
+}
+
+/// Times every phase of the selected days and writes the summary as CSV.
+fn bench(args: &Args) -> Result<()> {
+    if args.iterations == 0 {
+        return Err("At least one iteration is required".into());
+    }
+    let mut csv = bench::CSV_HEADER.to_owned();
+    println!(
+        "{:<5} {:<6} {:>12} {:>12} {:>12}",
+        "day", "phase", "min", "median", "max"
+    );
+    for day in selected_days(args)? {
+        let timings = load_input(args, day.day).and_then(|input| {
+            (day.bench)(&input, args.mode, args.iterations).map_err(|e| e.in_file(&input.name))
+        });
+        let timings = match timings {
+            Ok(timings) => timings,
+            Err(e) => {
+                println!("day{:02} error: {}", day.day, e);
+                continue;
+            }
+        };
+        for &phase in Phase::ALL.iter() {
+            if let Some(summary) = Summary::new(timings.samples(phase).to_vec()) {
+                println!(
+                    "{:<5} {:<6} {:>12?} {:>12?} {:>12?}",
+                    format!("day{:02}", day.day),
+                    phase.name(),
+                    summary.min,
+                    summary.median,
+                    summary.max
+                );
+                bench::csv_row(&mut csv, day.day, phase, args.iterations, &summary);
+            }
+        }
+    }
+    Ok(write(&args.output, csv)?)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = parse_args(args().skip(1))?;
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    };
+
+    match args.command {
+        Command::Run if args.all => {
+            for day in days::DAYS.iter() {
+                match load_input(&args, day.day) {
+                    Ok(input) => run(day, &parts, &input, &args),
+                    Err(e) => eprintln!("day{:02}: {}", day.day, e),
+                }
+            }
+        }
+        Command::Run => {
+            let day = args.day.ok_or(USAGE)?;
+            let day = days::find(day).ok_or(format!("Unknown day: {}", day))?;
+            let input = read_input(args.input.as_deref(), day.day)?;
+            run(day, &parts, &input, &args);
+        }
+        Command::Verify => {
+            let path = answers_path(&args);
+            let mut answers = Answers::load(&path)?;
+            let failures = verify(&args, &parts, &mut answers)?;
+            if args.record {
+                answers.save(&path)?;
+            }
+            if failures > 0 {
+                return Err(format!("{} answers failed verification", failures).into());
+            }
+        }
+        Command::Bench => bench(&args)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,510 @@
+use aoc_common::{collect, Diagnostic, Error, Mode, Result, Solution, Source};
+use std::{collections::HashMap, fmt, ops::Range};
+
+pub const TARGET: i32 = 2020;
+
+/// A number of the expense report with its line, counted from 1.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Entry {
+    pub line: usize,
+    pub value: i32,
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (line {})", self.value, self.line)
+    }
+}
+
+/// Parses one number per line, sorted by value and then line.
+pub fn parse_input(input: &str, mode: Mode) -> Result<Vec<Entry>, Diagnostic> {
+    let source = Source::new(input);
+    let entries = input.lines().enumerate().map(|(i, line)| {
+        let value = source.parse(line, "number")?;
+        Ok(Entry { line: i + 1, value })
+    });
+    let mut entries = collect(entries, mode)?;
+    sort(&mut entries);
+    Ok(entries)
+}
+
+fn sort(entries: &mut [Entry]) {
+    entries.sort_unstable_by_key(|entry| (entry.value, entry.line));
+}
+
+/// Which combinations [`k_sum`] looks for.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Matches {
+    First,
+    /// Every distinct one.
+    All,
+}
+
+/// Whether entries with the same value count as different ones.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Duplicates {
+    /// Every line is its own entry, so a value can be used as often as it
+    /// appears and combinations are listed for every choice of lines.
+    Separate,
+    /// A value is used at most once, from the first line it appears on.
+    Merge,
+}
+
+/// The entries a combination is looked for in, sorted by value and line.
+fn candidates(entries: &[Entry], duplicates: Duplicates) -> Vec<Entry> {
+    let mut sorted = entries.to_vec();
+    sort(&mut sorted);
+    if duplicates == Duplicates::Merge {
+        sorted.dedup_by_key(|entry| entry.value);
+    }
+    sorted
+}
+
+/// Combinations of `k` different entries summing to `target`, every one
+/// sorted by value and line, in that order too.
+///
+/// Pairs are found with two pointers walking the sorted entries towards each
+/// other, larger combinations fix their smallest entry and look for the rest.
+pub fn k_sum(
+    entries: &[Entry],
+    k: usize,
+    target: i32,
+    matches: Matches,
+    duplicates: Duplicates,
+) -> Vec<Vec<Entry>> {
+    let sorted = candidates(entries, duplicates);
+    let mut found = Vec::new();
+    k_sum_sorted(
+        &sorted,
+        k,
+        target as i64,
+        matches,
+        &mut Vec::new(),
+        &mut found,
+    );
+    found
+}
+
+/// Adds combinations completing `prefix` to `found`, returns whether to stop.
+fn k_sum_sorted(
+    entries: &[Entry],
+    k: usize,
+    target: i64,
+    matches: Matches,
+    prefix: &mut Vec<Entry>,
+    found: &mut Vec<Vec<Entry>>,
+) -> bool {
+    let mut add = |rest: &[Entry]| {
+        found.push(prefix.iter().chain(rest).copied().collect());
+        matches == Matches::First
+    };
+    match k {
+        0 => target == 0 && add(&[]),
+        1 => {
+            let start = entries.partition_point(|entry| (entry.value as i64) < target);
+            let equal = entries[start..]
+                .iter()
+                .take_while(|entry| entry.value as i64 == target);
+            equal.into_iter().any(|&entry| add(&[entry]))
+        }
+        2 => {
+            let (mut start, mut end) = (0, entries.len());
+            while start + 1 < end {
+                let (c1, c2) = (entries[start].value, entries[end - 1].value);
+                match c1 as i64 + c2 as i64 {
+                    sum if sum < target => start += 1,
+                    sum if sum > target => end -= 1,
+                    _ if c1 == c2 => {
+                        // every entry left has this value, any two of them match
+                        for i in start..end {
+                            for j in i + 1..end {
+                                if add(&[entries[i], entries[j]]) {
+                                    return true;
+                                }
+                            }
+                        }
+                        return false;
+                    }
+                    _ => {
+                        let low = start
+                            + entries[start..end]
+                                .iter()
+                                .take_while(|e| e.value == c1)
+                                .count();
+                        let high = end
+                            - entries[start..end]
+                                .iter()
+                                .rev()
+                                .take_while(|e| e.value == c2)
+                                .count();
+                        for i in start..low {
+                            for j in high..end {
+                                if add(&[entries[i], entries[j]]) {
+                                    return true;
+                                }
+                            }
+                        }
+                        start = low;
+                        end = high;
+                    }
+                }
+            }
+            false
+        }
+        _ => {
+            for (i, &entry) in entries.iter().enumerate() {
+                // every other entry is at least as large as this one
+                if entry.value as i64 * k as i64 > target {
+                    break;
+                }
+                prefix.push(entry);
+                let stop = k_sum_sorted(
+                    &entries[i + 1..],
+                    k - 1,
+                    target - entry.value as i64,
+                    matches,
+                    prefix,
+                    found,
+                );
+                prefix.pop();
+                if stop {
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+/// Same as [`k_sum`], meeting in the middle: the sums of every combination of
+/// `k / 2` entries are hashed and looked up from the combinations of the rest.
+///
+/// It takes about n^(k - k / 2) steps where [`k_sum`] needs n^(k - 1), so it's
+/// worth it from four entries on, n² steps instead of n³. In exchange it keeps
+/// the indices of every combination of `k / 2` entries, a few megabytes for
+/// four out of a thousand; for two or three entries it isn't any faster.
+pub fn k_sum_hashed(
+    entries: &[Entry],
+    k: usize,
+    target: i32,
+    matches: Matches,
+    duplicates: Duplicates,
+) -> Vec<Vec<Entry>> {
+    let sorted = candidates(entries, duplicates);
+    let sum = |indices: &[usize]| indices.iter().map(|&i| sorted[i].value as i64).sum::<i64>();
+    let (low, high) = (k / 2, k - k / 2);
+    // halves of `low` indices one after another, and their sums
+    let (mut halves, mut sums) = (Vec::new(), Vec::new());
+    for_each_combination(sorted.len(), low, |half| {
+        halves.extend_from_slice(half);
+        sums.push(sum(half));
+        false
+    });
+    // half numbers ordered by their sum, and where every sum's run of them is
+    let mut order: Vec<usize> = (0..sums.len()).collect();
+    order.sort_unstable_by_key(|&h| sums[h]);
+    let mut by_sum: HashMap<i64, Range<usize>> = HashMap::new();
+    for (pos, &h) in order.iter().enumerate() {
+        by_sum.entry(sums[h]).or_insert(pos..pos).end = pos + 1;
+    }
+
+    let mut found = Vec::new();
+    // the high half starts past the low one, so every set of indices is tried once
+    for_each_combination(sorted.len(), high, |rest| {
+        let first = rest.first().map_or(sorted.len(), |&i| i);
+        let run = by_sum.get(&(target as i64 - sum(rest))).cloned();
+        for &h in &order[run.unwrap_or(0..0)] {
+            let half = &halves[h * low..(h + 1) * low];
+            if half.last().map_or(true, |&i| i < first) {
+                found.push(half.iter().chain(rest).copied().collect::<Vec<_>>());
+                if matches == Matches::First {
+                    return true;
+                }
+            }
+        }
+        false
+    });
+    // index order is the order of `k_sum`
+    found.sort_unstable();
+    found
+        .into_iter()
+        .map(|indices| indices.iter().map(|&i| sorted[i]).collect())
+        .collect()
+}
+
+/// Calls `visit` with every increasing sequence of `k` indices below `n`, until
+/// it returns true.
+fn for_each_combination(n: usize, k: usize, mut visit: impl FnMut(&[usize]) -> bool) {
+    if k > n {
+        return;
+    }
+    let mut indices: Vec<usize> = (0..k).collect();
+    loop {
+        if visit(&indices) {
+            return;
+        }
+        // move the last index which still can one further, and reset the ones after it
+        let i = match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+            Some(i) => i,
+            None => return,
+        };
+        indices[i] += 1;
+        for j in i + 1..k {
+            indices[j] = indices[j - 1] + 1;
+        }
+    }
+}
+
+/// The product of the values of a combination, or `None` if it overflows.
+pub fn product(combination: &[Entry]) -> Option<i128> {
+    combination.iter().try_fold(1i128, |product, entry| {
+        product.checked_mul(entry.value as i128)
+    })
+}
+
+pub fn solve2(entries: &[Entry], target: i32) -> Option<(Entry, Entry)> {
+    let found = k_sum(entries, 2, target, Matches::First, Duplicates::Separate);
+    found.first().map(|c| (c[0], c[1]))
+}
+
+pub fn solve3(entries: &[Entry], target: i32) -> Option<(Entry, Entry, Entry)> {
+    let found = k_sum(entries, 3, target, Matches::First, Duplicates::Separate);
+    found.first().map(|c| (c[0], c[1], c[2]))
+}
+
+/// The product of the first `k` entries summing to [`TARGET`].
+fn answer(entries: &[Entry], k: usize) -> Result<String> {
+    let found = k_sum(entries, k, TARGET, Matches::First, Duplicates::Separate);
+    let combination = found.first().ok_or(Error::NoSolution)?;
+    match product(combination) {
+        Some(product) => Ok(product.to_string()),
+        None => Err(format!("Product of {:?} overflows", combination).into()),
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<Entry>;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input, mode)?)
+    }
+
+    fn part1(entries: &Self::Input) -> Result<String> {
+        answer(entries, 2)
+    }
+
+    fn part2(entries: &Self::Input) -> Result<String> {
+        answer(entries, 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "1721\n979\n366\n299\n675\n1456\n";
+
+    /// Entries on consecutive lines from 1.
+    fn entries(values: &[i32]) -> Vec<Entry> {
+        let lines = 1..;
+        lines
+            .zip(values)
+            .map(|(line, &value)| Entry { line, value })
+            .collect()
+    }
+
+    fn values(found: &[Vec<Entry>]) -> Vec<Vec<i32>> {
+        found
+            .iter()
+            .map(|c| c.iter().map(|e| e.value).collect())
+            .collect()
+    }
+
+    fn lines(found: &[Vec<Entry>]) -> Vec<Vec<usize>> {
+        found
+            .iter()
+            .map(|c| c.iter().map(|e| e.line).collect())
+            .collect()
+    }
+
+    #[test]
+    fn pair_summing_to_target() {
+        let entries = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let (c1, c2) = solve2(&entries, TARGET).unwrap();
+        assert_eq!(
+            c1,
+            Entry {
+                line: 4,
+                value: 299
+            }
+        );
+        assert_eq!(
+            c2,
+            Entry {
+                line: 1,
+                value: 1721
+            }
+        );
+    }
+
+    #[test]
+    fn triple_summing_to_target() {
+        let entries = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let (c1, c2, c3) = solve3(&entries, TARGET).unwrap();
+        assert_eq!((c1.value, c2.value, c3.value), (366, 675, 979));
+        assert_eq!((c1.line, c2.line, c3.line), (3, 5, 2));
+    }
+
+    #[test]
+    fn no_match() {
+        assert_eq!(solve2(&entries(&[1, 2, 3]), TARGET), None);
+        assert_eq!(solve2(&[], TARGET), None);
+        assert_eq!(solve3(&entries(&[1, 2]), TARGET), None);
+    }
+
+    #[test]
+    fn entries_are_used_once() {
+        assert_eq!(solve2(&entries(&[1010, 5]), TARGET), None);
+        let (c1, c2) = solve2(&entries(&[1010, 1010]), TARGET).unwrap();
+        assert_eq!((c1.line, c2.line), (1, 2));
+        assert_eq!(solve3(&entries(&[1000, 10, 600]), TARGET), None);
+    }
+
+    #[test]
+    fn duplicates() {
+        let entries = entries(&[1010, 7, 1010, 2013, 1010, 7]);
+        let separate = k_sum(&entries, 2, TARGET, Matches::All, Duplicates::Separate);
+        assert_eq!(
+            lines(&separate),
+            vec![vec![2, 4], vec![6, 4], vec![1, 3], vec![1, 5], vec![3, 5]]
+        );
+        let merged = k_sum(&entries, 2, TARGET, Matches::All, Duplicates::Merge);
+        assert_eq!(lines(&merged), vec![vec![2, 4]]);
+        let single = k_sum(&entries, 1, 7, Matches::All, Duplicates::Separate);
+        assert_eq!(lines(&single), vec![vec![2], vec![6]]);
+        let triples = k_sum(&entries, 3, 3030, Matches::All, Duplicates::Separate);
+        // 7 + 1010 + 2013 for every choice of lines, then the three 1010
+        assert_eq!(triples.len(), 7);
+        assert_eq!(lines(&triples[..2]), vec![vec![2, 1, 4], vec![2, 3, 4]]);
+        assert_eq!(lines(&triples[6..]), vec![vec![1, 3, 5]]);
+        let merged = k_sum(&entries, 3, 3030, Matches::All, Duplicates::Merge);
+        assert_eq!(lines(&merged), vec![vec![2, 1, 4]]);
+    }
+
+    #[test]
+    fn any_number_of_entries() {
+        let entries = entries(&[1, 2, 3, 4, 5, 6, 2, -1]);
+        let k_sum = |k, target, matches| k_sum(&entries, k, target, matches, Duplicates::Merge);
+        // 2 + 2 + 2 would need a third 2
+        assert_eq!(
+            values(&k_sum(3, 6, Matches::All)),
+            vec![
+                vec![-1, 1, 6],
+                vec![-1, 2, 5],
+                vec![-1, 3, 4],
+                vec![1, 2, 3]
+            ]
+        );
+        assert_eq!(values(&k_sum(3, 6, Matches::First)), vec![vec![-1, 1, 6]]);
+        assert_eq!(values(&k_sum(1, 4, Matches::All)), vec![vec![4]]);
+        assert_eq!(values(&k_sum(0, 0, Matches::All)), vec![Vec::<i32>::new()]);
+        assert_eq!(values(&k_sum(9, 22, Matches::All)), Vec::<Vec<i32>>::new());
+        assert_eq!(
+            values(&k_sum(2, 4, Matches::All)),
+            vec![vec![-1, 5], vec![1, 3]]
+        );
+        let pairs = super::k_sum(&entries, 2, 4, Matches::All, Duplicates::Separate);
+        assert_eq!(lines(&pairs), vec![vec![8, 5], vec![1, 3], vec![2, 7]]);
+        let all = super::k_sum(&entries, 8, 22, Matches::All, Duplicates::Separate);
+        assert_eq!(values(&all), vec![vec![-1, 1, 2, 2, 3, 4, 5, 6]]);
+    }
+
+    fn combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+        let mut all = Vec::new();
+        for_each_combination(n, k, |indices| {
+            all.push(indices.to_vec());
+            false
+        });
+        all
+    }
+
+    #[test]
+    fn hashed_matches_pointers() {
+        let mut state = 7u32;
+        let numbers: Vec<i32> = (0..40)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+                (state >> 16) as i32 % 50 - 10
+            })
+            .collect();
+        let entries = entries(&numbers);
+        for &duplicates in &[Duplicates::Separate, Duplicates::Merge] {
+            for k in 0..=4 {
+                for target in (-5..60).step_by(4) {
+                    let expected = k_sum(&entries, k, target, Matches::All, duplicates);
+                    assert_eq!(
+                        k_sum_hashed(&entries, k, target, Matches::All, duplicates),
+                        expected,
+                        "{} {}",
+                        k,
+                        target
+                    );
+                    let first = k_sum_hashed(&entries, k, target, Matches::First, duplicates);
+                    assert_eq!(first.len(), expected.len().min(1));
+                    assert!(first.iter().all(|c| expected.contains(c)));
+                }
+            }
+        }
+        assert_eq!(combinations(4, 2).len(), 6);
+        assert_eq!(combinations(2, 3), Vec::<Vec<usize>>::new());
+        assert_eq!(combinations(3, 0), vec![Vec::<usize>::new()]);
+    }
+
+    #[test]
+    fn products() {
+        assert_eq!(product(&entries(&[299, 1721])), Some(514579));
+        assert_eq!(product(&[]), Some(1));
+        let large = entries(&[i32::MIN; 5]);
+        assert_eq!(product(&large[..4]), Some((i32::MIN as i128).pow(4)));
+        assert_eq!(product(&large), None);
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let input = "1721\n979\n\n366x\n";
+        assert_eq!(
+            parse_input(input, Mode::Lenient).unwrap(),
+            vec![
+                Entry {
+                    line: 2,
+                    value: 979
+                },
+                Entry {
+                    line: 1,
+                    value: 1721
+                }
+            ]
+        );
+        let err = parse_input(input, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.found, "");
+        let skipped = parse_input("x\n1010\n\n1010\n", Mode::Lenient).unwrap();
+        let (c1, c2) = solve2(&skipped, TARGET).unwrap();
+        assert_eq!((c1.line, c2.line), (2, 4));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(
+            Day1::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(),
+            "514579"
+        );
+        assert_eq!(
+            Day1::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(),
+            "241861950"
+        );
+    }
+}
@@ -1,36 +1,85 @@
-use std::{env::args, fs::read_to_string};
-
-fn solve2(numbers: &[i32], target: i32) -> Option<(i32, i32)> {
-    let (mut start, mut end) = (0, numbers.len() - 1);
-    while start < end {
-        match (numbers[start], numbers[end]) {
-            (c1, c2) if c1 + c2 == target => return Some((c1, c2)),
-            (c1, c2) if c1 + c2 > target => end -= 1,
-            _ => start += 1,
-        }
-    }
-    None
-}
+use aoc_common::{print_json, Cli, Format, Opt, Result};
+use day1::{k_sum, k_sum_hashed, product, Day1, Duplicates, Entry, Matches, TARGET};
+
+const USAGE: &str = "\
+usage: day1 [<input>|-] [<target>] [-k <n>] [--all] [--hashed]
+            [--merge-duplicates] [--format <human|json>] [--strict]
+
+-k looks for <n> entries summing to the target instead of the two parts,
+--all lists every combination instead of the first one and --hashed meets in
+the middle, which is faster on big inputs.
+
+Every line is a separate entry, so a value on two lines can be used twice;
+--merge-duplicates uses every value at most once, from its first line.";
+
+const OPTIONS: &[Opt] = &[
+    Opt::value("<target>"),
+    Opt::value("-k"),
+    Opt::flag("--all"),
+    Opt::flag("--hashed").any_format(),
+    Opt::flag("--merge-duplicates"),
+];
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input = read_to_string(args().nth(1).ok_or("No input file")?)?;
-    let target: i32 = args().nth(2).ok_or("No input target number")?.parse()?;
+    let cli = Cli::from_env_with(USAGE, OPTIONS)?;
+    let input = cli.input(1)?;
+    if cli.format == Format::Json {
+        print_json::<Day1>(1, &input, cli.mode);
+        return Ok(());
+    }
+    let target = cli.value("<target>").map_or(Ok(TARGET), str::parse)?;
+    let k = cli.value("-k").map(str::parse).transpose()?;
+    let matches = if cli.flag("--all") {
+        Matches::All
+    } else {
+        Matches::First
+    };
+    let duplicates = if cli.flag("--merge-duplicates") {
+        Duplicates::Merge
+    } else {
+        Duplicates::Separate
+    };
 
-    let mut numbers: Vec<_> = input
-        .lines()
-        .filter_map(|line| str::parse::<i32>(&line).ok())
-        .collect();
-    numbers.sort_unstable();
+    let entries = input.parse::<Day1>(cli.mode)?;
+    let k_sum = if cli.flag("--hashed") {
+        k_sum_hashed
+    } else {
+        k_sum
+    };
 
-    if let Some((c1, c2)) = solve2(&numbers, target) {
-        println!("p1: {} * {} == {}", c1, c2, c1 * c2);
+    if let Some(k) = k {
+        let found = k_sum(&entries, k, target, matches, duplicates);
+        for combination in &found {
+            let terms: Vec<_> = combination.iter().map(Entry::to_string).collect();
+            println!(
+                "{} == {}, product {}",
+                terms.join(" + "),
+                target,
+                describe(product(combination))
+            );
+        }
+        if found.is_empty() {
+            println!("no {} entries sum to {}", k, target);
+        }
+        return Ok(());
     }
 
-    if let Some((cur, c1, c2)) = (0..numbers.len() - 2)
-        .filter_map(|i| solve2(&numbers[i..], target - numbers[i]).map(|(a, b)| (numbers[i], a, b)))
-        .next()
-    {
-        println!("p2: {} * {} * {} == {}", cur, c1, c2, cur * c1 * c2);
+    for (part, k) in [("p1", 2), ("p2", 3)].iter().copied() {
+        let found = k_sum(&entries, k, target, Matches::First, duplicates);
+        if let Some(combination) = found.first() {
+            let terms: Vec<_> = combination.iter().map(Entry::to_string).collect();
+            println!(
+                "{}: {} == {}",
+                part,
+                terms.join(" * "),
+                describe(product(combination))
+            );
+        }
     }
     Ok(())
 }
+
+/// A checked product, or why there is none.
+fn describe(product: Option<i128>) -> String {
+    product.map_or_else(|| "overflow".to_string(), |product| product.to_string())
+}
@@ -0,0 +1,115 @@
+use aoc_common::{parse_lines, Error, Mode, Result, Solution};
+
+pub fn solve(numbers: &[i64]) -> impl Iterator<Item = i64> + '_ {
+    numbers.iter().scan(0, |state, &val| {
+        let res = val - *state;
+        *state = val;
+        Some(res)
+    })
+}
+
+/// Multiplies the 1 and 3 jolt differences of the chain of all adapters, if
+/// none of them is more than 3.
+pub fn part1(numbers: &[i64]) -> Option<i32> {
+    let (ones, threes) = solve(numbers).try_fold((0, 1), |(ones, threes), val| match val {
+        1 => Some((ones + 1, threes)),
+        3 => Some((ones, threes + 1)),
+        val if val > 3 => None,
+        _ => Some((ones, threes)),
+    })?;
+    Some(ones * threes)
+}
+
+pub fn part2(
+    numbers: &[i64],
+    cache: &mut Vec<Option<i64>>,
+    idx: usize,
+    last: i64,
+    target: i64,
+) -> i64 {
+    let mut total = 0;
+    if let Some(val) = cache.get(idx).cloned().flatten() {
+        return val;
+    }
+    for (i, n) in numbers.iter().enumerate() {
+        if n - last <= 3 {
+            let res = part2(&numbers[i + 1..], cache, idx + i + 1, *n, target);
+            total += res;
+        } else {
+            break;
+        }
+    }
+    if target - last <= 3 {
+        total += 1;
+    } else if total != 0 {
+        cache[idx] = Some(total);
+    }
+    total
+}
+
+/// Counts all adapter chains, `numbers` has to be sorted.
+pub fn arrangements(numbers: &[i64]) -> Option<i64> {
+    let target = numbers.last()? + 3;
+    let mut cache = vec![None; numbers.len()];
+    Some(part2(numbers, &mut cache, 0, 0, target))
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str, _mode: Mode) -> Result<Self::Input> {
+        let mut numbers = parse_lines(input)?;
+        numbers.sort_unstable();
+        Ok(numbers)
+    }
+
+    fn part1(numbers: &Self::Input) -> Result<String> {
+        Ok(part1(numbers).ok_or(Error::NoSolution)?.to_string())
+    }
+
+    fn part2(numbers: &Self::Input) -> Result<String> {
+        Ok(arrangements(numbers).ok_or(Error::NoSolution)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const SMALL: &str = "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4\n";
+    const LARGE: &str = "\
+28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3\n";
+
+    #[test]
+    fn small_example() {
+        assert_eq!(Day10::solve(SMALL, Part::One, Mode::Strict).unwrap(), "35");
+        assert_eq!(Day10::solve(SMALL, Part::Two, Mode::Strict).unwrap(), "8");
+    }
+
+    #[test]
+    fn large_example() {
+        assert_eq!(Day10::solve(LARGE, Part::One, Mode::Strict).unwrap(), "220");
+        assert_eq!(
+            Day10::solve(LARGE, Part::Two, Mode::Strict).unwrap(),
+            "19208"
+        );
+    }
+
+    #[test]
+    fn gap() {
+        assert_eq!(part1(&[1, 5]), None);
+        assert!(matches!(
+            Day10::solve("1\n5\n", Part::One, Mode::Strict),
+            Err(Error::NoSolution)
+        ));
+    }
+
+    #[test]
+    fn single_adapter() {
+        assert_eq!(arrangements(&[3]), Some(1));
+        assert_eq!(arrangements(&[]), None);
+    }
+}
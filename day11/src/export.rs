@@ -0,0 +1,136 @@
+use crate::{image::Image, Map, Tile};
+use aoc_common::{Error, Result};
+use std::{
+    io::{self, Write},
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+fn color(tile: &Tile) -> [u8; 3] {
+    match tile {
+        Tile::Floor => [40, 40, 40],
+        Tile::Empty => [60, 180, 75],
+        Tile::Occupied => [220, 50, 47],
+    }
+}
+
+/// Draws every tile as a `scale`x`scale` square, floor gray, empty seats
+/// green and occupied ones red.
+pub fn frame(map: &Map, scale: usize) -> Image {
+    Image::from_grid(map.tiles(), scale, color)
+}
+
+/// File format of exported frames.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FrameFormat {
+    Ppm,
+    Png,
+}
+
+impl FrameFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            FrameFormat::Ppm => "ppm",
+            FrameFormat::Png => "png",
+        }
+    }
+
+    pub fn encode(self, image: &Image) -> Result<Vec<u8>> {
+        match self {
+            FrameFormat::Ppm => Ok(image.to_ppm()),
+            FrameFormat::Png => image.to_png(),
+        }
+    }
+}
+
+impl FromStr for FrameFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ppm" => Ok(FrameFormat::Ppm),
+            "png" => Ok(FrameFormat::Png),
+            _ => Err(format!("Invalid frame format: {}", s).into()),
+        }
+    }
+}
+
+/// Plays rounds in a terminal, moving the cursor back up to draw every
+/// round over the previous one.
+pub struct Replay<W: Write> {
+    out: W,
+    delay: Duration,
+    /// lines taken by the last round drawn
+    lines: usize,
+}
+
+impl<W: Write> Replay<W> {
+    pub fn new(out: W, delay: Duration) -> Self {
+        Replay {
+            out,
+            delay,
+            lines: 0,
+        }
+    }
+
+    /// Draws `map` as round `round` and waits for the delay.
+    pub fn frame(&mut self, round: usize, map: &Map) -> io::Result<()> {
+        if self.lines > 0 {
+            write!(self.out, "\x1b[{}A", self.lines)?;
+        }
+        writeln!(self.out, "round {}", round)?;
+        write!(self.out, "{}", map.tiles())?;
+        self.out.flush()?;
+        self.lines = map.tiles().height() + 1;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_input, Outcome, Seating, Strategy};
+    use aoc_common::Mode;
+
+    #[test]
+    fn replay() {
+        let map = parse_input("L.L\nLLL\n", Mode::Strict).unwrap();
+        let mut replay = Replay::new(Vec::new(), Duration::from_millis(0));
+        let mut rounds = Vec::new();
+        let mut observe = |seating: &Seating| -> Result<()> {
+            rounds.push(seating.rounds());
+            Ok(replay.frame(seating.rounds(), &seating.map())?)
+        };
+        let outcome = Seating::new(&map, &Strategy::Close).run_observed(None, &mut observe);
+        assert_eq!(outcome.unwrap(), Outcome::Converged { rounds: 2 });
+        assert_eq!(rounds, vec![0, 1, 2]);
+        let out = String::from_utf8(replay.into_inner()).unwrap();
+        assert_eq!(
+            out,
+            "round 0\nL.L\nLLL\n\x1b[3Around 1\n#.#\n###\n\x1b[3Around 2\n#.#\n#L#\n"
+        );
+    }
+
+    #[test]
+    fn frames() {
+        let map = parse_input("L.L\nLLL\n", Mode::Strict).unwrap();
+        let image = frame(&map, 2);
+        assert_eq!((image.width(), image.height()), (6, 4));
+        assert!(FrameFormat::Png
+            .encode(&image)
+            .unwrap()
+            .starts_with(b"\x89PNG"));
+        assert!(FrameFormat::Ppm
+            .encode(&image)
+            .unwrap()
+            .starts_with(b"P6\n6 4\n"));
+        assert_eq!("ppm".parse::<FrameFormat>().unwrap().extension(), "ppm");
+        assert!("gif".parse::<FrameFormat>().is_err());
+    }
+}
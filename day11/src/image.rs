@@ -0,0 +1,113 @@
+use aoc_common::{Grid, Result};
+use std::convert::TryFrom;
+
+/// RGB image which can be written as a binary PPM or a PNG file.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    /// red, green and blue of every pixel, row by row
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Black image.
+    pub fn new(width: usize, height: usize) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![0; width * height * 3],
+        }
+    }
+
+    /// Draws every cell of `grid` as a `scale`x`scale` square colored by `color`.
+    pub fn from_grid<T>(
+        grid: &Grid<T>,
+        scale: usize,
+        mut color: impl FnMut(&T) -> [u8; 3],
+    ) -> Self {
+        let mut image = Image::new(grid.width() * scale, grid.height() * scale);
+        for (x, y) in grid.positions() {
+            let rgb = color(&grid[(x, y)]);
+            for py in y * scale..(y + 1) * scale {
+                for px in x * scale..(x + 1) * scale {
+                    image.set(px, py, rgb);
+                }
+            }
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, rgb: [u8; 3]) {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel outside of the image"
+        );
+        let idx = (y * self.width + x) * 3;
+        self.pixels[idx..idx + 3].copy_from_slice(&rgb);
+    }
+
+    /// Binary (`P6`) PPM file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend_from_slice(&self.pixels);
+        out
+    }
+
+    /// PNG file.
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let too_large = || {
+            format!(
+                "Image of {}x{} is too large for PNG",
+                self.width, self.height
+            )
+        };
+        let width = u32::try_from(self.width).map_err(|_| too_large())?;
+        let height = u32::try_from(self.height).map_err(|_| too_large())?;
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(|e| e.to_string())?;
+        writer.finish().map_err(|e| e.to_string())?;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ppm_and_png() {
+        let mut image = Image::new(2, 1);
+        image.set(1, 0, [255, 0, 10]);
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\0\0\0\xff\0\x0a".to_vec());
+
+        let grid = Grid::new(2, 2, vec![true, false, false, true]);
+        let image = Image::from_grid(&grid, 3, |&b| if b { [255; 3] } else { [0; 3] });
+        assert_eq!((image.width(), image.height()), (6, 6));
+        let png = image.to_png().unwrap();
+        let mut reader = png::Decoder::new(&png[..]).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (6, 6));
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+        assert_eq!(
+            &pixels[..12],
+            &[255, 255, 255, 255, 255, 255, 255, 255, 255, 0, 0, 0]
+        );
+        assert_eq!(&pixels[6 * 3 * 5 + 15..6 * 3 * 6], &[255, 255, 255]);
+    }
+}
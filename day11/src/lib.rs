@@ -0,0 +1,821 @@
+use aoc_common::{Diagnostic, Error, Grid, Mode, Result, Solution, SURROUNDING};
+pub mod export;
+pub mod image;
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+    str::FromStr,
+};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum Tile {
+    Occupied,
+    Empty,
+    #[default]
+    Floor,
+}
+
+/// Rule of the puzzle's first or second part.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Strategy {
+    Visible,
+    Close,
+}
+
+/// How seats change every round.
+pub trait SeatingRule {
+    /// Seats whose occupants count as neighbors of the seat at `pos`.
+    ///
+    /// They're computed once per simulation, so they may only depend on where
+    /// the seats are, not on whether they're occupied.
+    fn neighbors(&self, map: &Map, pos: (usize, usize)) -> Vec<(usize, usize)>;
+
+    /// Whether an empty seat with `occupied` occupied neighbors gets taken.
+    fn birth(&self, occupied: usize) -> bool {
+        occupied == 0
+    }
+
+    /// Number of occupied neighbors from which an occupied seat is left.
+    fn tolerance(&self) -> usize;
+}
+
+/// Seats considered neighbors of a seat.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Neighborhood {
+    /// The eight surrounding tiles.
+    Adjacent,
+    /// The first seat seen in each of the eight directions.
+    LineOfSight,
+    /// Every tile at most this many rows and columns away.
+    Radius(usize),
+    /// Every tile at most this many steps away, moving orthogonally.
+    Manhattan(usize),
+}
+
+impl Neighborhood {
+    /// Seats around `pos`, never `pos` itself.
+    ///
+    /// Across a torus or a mirror the same seat can be reached more than once,
+    /// it's then listed, and counted, as often.
+    pub fn seats(self, map: &Map, pos: (usize, usize), boundary: Boundary) -> Vec<(usize, usize)> {
+        let tiles = &map.tiles;
+        let is_seat =
+            |&neighbor: &(usize, usize)| neighbor != pos && tiles[neighbor] != Tile::Floor;
+        let k = match self {
+            Neighborhood::Adjacent => 1,
+            Neighborhood::LineOfSight => {
+                return SURROUNDING
+                    .iter()
+                    .filter_map(|&dir| boundary.sight(tiles, pos, dir))
+                    .collect()
+            }
+            Neighborhood::Radius(k) | Neighborhood::Manhattan(k) => k as isize,
+        };
+        (-k..=k)
+            .flat_map(|dy| (-k..=k).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| (dx, dy) != (0, 0))
+            .filter(|(dx, dy)| match self {
+                Neighborhood::Manhattan(_) => dx.abs() + dy.abs() <= k,
+                _ => true,
+            })
+            .filter_map(|(dx, dy)| {
+                let x = boundary.fold(pos.0 as isize + dx, tiles.width())?;
+                let y = boundary.fold(pos.1 as isize + dy, tiles.height())?;
+                Some((x, y))
+            })
+            .filter(is_seat)
+            .collect()
+    }
+}
+
+/// What lies past the edges of the map.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Boundary {
+    /// Nothing, as if the map was walled in.
+    Wall,
+    /// The opposite edge, as if the map was a torus.
+    Torus,
+    /// The map mirrored at its edge, the edge itself not repeated.
+    Reflect,
+}
+
+impl Boundary {
+    /// Index of coordinate `c` on an axis of length `len`.
+    fn fold(self, c: isize, len: usize) -> Option<usize> {
+        let len = len as isize;
+        match self {
+            _ if (0..len).contains(&c) => Some(c as usize),
+            Boundary::Wall => None,
+            Boundary::Torus => Some(c.rem_euclid(len) as usize),
+            Boundary::Reflect if len == 1 => Some(0),
+            Boundary::Reflect => {
+                let period = 2 * (len - 1);
+                let c = c.rem_euclid(period);
+                Some(if c < len { c } else { period - c } as usize)
+            }
+        }
+    }
+
+    /// Moves `c` by `d` along an axis of length `len`, turning `d` around at a mirror.
+    fn step(self, c: usize, d: isize, len: usize) -> Option<(usize, isize)> {
+        let next = c as isize + d;
+        match self {
+            _ if (0..len as isize).contains(&next) => Some((next as usize, d)),
+            Boundary::Wall => None,
+            Boundary::Torus => Some((next.rem_euclid(len as isize) as usize, d)),
+            Boundary::Reflect => Some(match self.fold(next, len) {
+                Some(bounced) if len > 1 => (bounced, -d),
+                _ => (c, d),
+            }),
+        }
+    }
+
+    /// First seat seen from `pos` looking in direction `dir`.
+    ///
+    /// Rays wrapping around or bouncing back and forth come back to `pos`
+    /// sooner or later, they then stop without having seen a seat.
+    fn sight(
+        self,
+        tiles: &Grid<Tile>,
+        pos: (usize, usize),
+        dir: (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let (mut x, mut y) = pos;
+        let (mut dx, mut dy) = dir;
+        // the longest cycle a ray can take on a torus or between mirrors
+        let limit = 4 * tiles.width() * tiles.height();
+        for _ in 0..limit {
+            let (nx, ndx) = self.step(x, dx, tiles.width())?;
+            let (ny, ndy) = self.step(y, dy, tiles.height())?;
+            (x, y, dx, dy) = (nx, ny, ndx, ndy);
+            if (x, y) == pos {
+                return None;
+            }
+            if tiles[(x, y)] != Tile::Floor {
+                return Some((x, y));
+            }
+        }
+        None
+    }
+}
+
+impl FromStr for Boundary {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "wall" => Ok(Boundary::Wall),
+            "torus" => Ok(Boundary::Torus),
+            "reflect" => Ok(Boundary::Reflect),
+            _ => Err(format!("Invalid boundary: {}", s).into()),
+        }
+    }
+}
+
+impl FromStr for Neighborhood {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, k) = match s.split_once(':') {
+            Some((name, k)) => (name, Some(k.parse()?)),
+            None => (s, None),
+        };
+        match (name, k) {
+            ("adjacent", None) => Ok(Neighborhood::Adjacent),
+            ("sight", None) => Ok(Neighborhood::LineOfSight),
+            ("radius", Some(k)) => Ok(Neighborhood::Radius(k)),
+            ("manhattan", Some(k)) => Ok(Neighborhood::Manhattan(k)),
+            _ => Err(format!("Invalid neighborhood: {}", s).into()),
+        }
+    }
+}
+
+/// Seating rule made of a neighborhood and thresholds.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Rule {
+    pub neighborhood: Neighborhood,
+    pub boundary: Boundary,
+    /// Most occupied neighbors an empty seat is still taken with.
+    pub birth: usize,
+    pub tolerance: usize,
+}
+
+impl SeatingRule for Rule {
+    fn neighbors(&self, map: &Map, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        self.neighborhood.seats(map, pos, self.boundary)
+    }
+
+    fn birth(&self, occupied: usize) -> bool {
+        occupied <= self.birth
+    }
+
+    fn tolerance(&self) -> usize {
+        self.tolerance
+    }
+}
+
+impl Strategy {
+    pub fn rule(self) -> Rule {
+        let (neighborhood, tolerance) = match self {
+            Strategy::Close => (Neighborhood::Adjacent, 4),
+            Strategy::Visible => (Neighborhood::LineOfSight, 5),
+        };
+        Rule {
+            neighborhood,
+            boundary: Boundary::Wall,
+            birth: 0,
+            tolerance,
+        }
+    }
+}
+
+impl SeatingRule for Strategy {
+    fn neighbors(&self, map: &Map, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        self.rule().neighbors(map, pos)
+    }
+
+    fn tolerance(&self) -> usize {
+        self.rule().tolerance
+    }
+}
+
+impl std::fmt::Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Tile::Empty => 'L',
+            Tile::Occupied => '#',
+            Tile::Floor => '.',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Map {
+    tiles: Grid<Tile>,
+}
+
+impl std::fmt::Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "w: {}", self.tiles.width())?;
+        writeln!(f, "h: {}", self.tiles.height())?;
+        write!(f, "{}", self.tiles)
+    }
+}
+
+impl Map {
+    /// The map after a single round of `rule`.
+    ///
+    /// Every call looks the neighbors up again, step a [`Seating`] instead to
+    /// simulate more rounds.
+    pub fn round(&self, rule: &dyn SeatingRule) -> Map {
+        let mut seating = Seating::new(self, rule);
+        seating.step();
+        seating.map()
+    }
+
+    pub fn occupied_neighbors(&self, rule: &dyn SeatingRule, pos: (usize, usize)) -> usize {
+        rule.neighbors(self, pos)
+            .into_iter()
+            .filter(|&pos| self.tiles[pos] == Tile::Occupied)
+            .count()
+    }
+
+    pub fn tiles(&self) -> &Grid<Tile> {
+        &self.tiles
+    }
+
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.iter().filter(|&&t| t == tile).count()
+    }
+}
+
+pub fn parse_input(input: &str, mode: Mode) -> Result<Map, Diagnostic> {
+    let tiles = Grid::parse(input, "'L', '#' or '.'", mode, |c| match c {
+        'L' => Some(Tile::Empty),
+        '#' => Some(Tile::Occupied),
+        '.' => Some(Tile::Floor),
+        _ => None,
+    })?;
+    Ok(Map { tiles })
+}
+
+/// Seats of a map changing round after round under a rule.
+///
+/// The neighbors of every seat are looked up once, and the tiles of the next
+/// round are written to a second buffer which is swapped in afterwards.
+pub struct Seating<'a> {
+    rule: &'a dyn SeatingRule,
+    width: usize,
+    height: usize,
+    /// indices of the tiles which aren't floor
+    seats: Vec<usize>,
+    /// neighbors of `seats[i]` are `neighbors[starts[i]..starts[i + 1]]`
+    starts: Vec<usize>,
+    neighbors: Vec<usize>,
+    tiles: Vec<Tile>,
+    next: Vec<Tile>,
+    rounds: usize,
+}
+
+impl<'a> Seating<'a> {
+    pub fn new(map: &Map, rule: &'a dyn SeatingRule) -> Self {
+        let width = map.tiles.width();
+        let index = |(x, y): (usize, usize)| y * width + x;
+        let mut seating = Seating {
+            rule,
+            width,
+            height: map.tiles.height(),
+            seats: Vec::new(),
+            starts: vec![0],
+            neighbors: Vec::new(),
+            tiles: map.tiles.iter().copied().collect(),
+            next: map.tiles.iter().copied().collect(),
+            rounds: 0,
+        };
+        for pos in map.tiles.positions() {
+            if map.tiles[pos] != Tile::Floor {
+                seating.seats.push(index(pos));
+                let neighbors = rule.neighbors(map, pos).into_iter();
+                seating.neighbors.extend(neighbors.map(index));
+                seating.starts.push(seating.neighbors.len());
+            }
+        }
+        seating
+    }
+
+    /// Writes the round after `tiles` to `next`, returning how many seats changed.
+    fn advance(&self, tiles: &[Tile], next: &mut [Tile]) -> usize {
+        let (mut changed, tolerance) = (0, self.rule.tolerance());
+        for (i, &seat) in self.seats.iter().enumerate() {
+            let occupied = self.neighbors[self.starts[i]..self.starts[i + 1]]
+                .iter()
+                .filter(|&&neighbor| tiles[neighbor] == Tile::Occupied)
+                .count();
+            let tile = match tiles[seat] {
+                Tile::Empty if self.rule.birth(occupied) => Tile::Occupied,
+                Tile::Occupied if occupied >= tolerance => Tile::Empty,
+                tile => tile,
+            };
+            changed += (tile != tiles[seat]) as usize;
+            next[seat] = tile;
+        }
+        changed
+    }
+
+    /// Simulates a round, returning how many seats changed.
+    pub fn step(&mut self) -> usize {
+        let mut next = mem::take(&mut self.next);
+        let changed = self.advance(&self.tiles, &mut next);
+        self.next = mem::replace(&mut self.tiles, next);
+        self.rounds += 1;
+        changed
+    }
+
+    /// The tiles `rounds` rounds after `tiles`.
+    fn replay(&self, tiles: &[Tile], rounds: usize) -> Vec<Tile> {
+        let (mut tiles, mut next) = (tiles.to_vec(), tiles.to_vec());
+        for _ in 0..rounds {
+            self.advance(&tiles, &mut next);
+            mem::swap(&mut tiles, &mut next);
+        }
+        tiles
+    }
+
+    /// Number of rounds simulated so far.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    fn hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.tiles.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Simulates until the seats settle, repeat an earlier round or
+    /// `max_rounds` rounds are done.
+    ///
+    /// Only a hash of every round's seats is kept, a round with the hash of
+    /// an earlier one is compared with it by replaying the rounds up to it.
+    pub fn run(&mut self, max_rounds: Option<usize>) -> Outcome {
+        self.run_observed(max_rounds, &mut |_| Ok(())).unwrap()
+    }
+
+    /// Like [`Seating::run`], but lets `observe` look at the seats before the
+    /// first round and after every round which changed them.
+    pub fn run_observed(
+        &mut self,
+        max_rounds: Option<usize>,
+        observe: &mut Observer,
+    ) -> Result<Outcome> {
+        let (initial, first) = (self.tiles.clone(), self.rounds);
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+        seen.entry(self.hash()).or_default().push(self.rounds);
+        observe(self)?;
+        loop {
+            if max_rounds.is_some_and(|max| self.rounds >= max) {
+                return Ok(Outcome::Capped {
+                    rounds: self.rounds,
+                });
+            }
+            if self.step() == 0 {
+                return Ok(Outcome::Converged {
+                    rounds: self.rounds - 1,
+                });
+            }
+            observe(self)?;
+            let rounds = seen.entry(self.hash()).or_default();
+            let repeated = rounds
+                .iter()
+                .find(|&&round| self.replay(&initial, round - first) == self.tiles);
+            if let Some(&start) = repeated {
+                return Ok(Outcome::Cycle {
+                    start,
+                    period: self.rounds - start,
+                });
+            }
+            rounds.push(self.rounds);
+        }
+    }
+
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.iter().filter(|&&t| t == tile).count()
+    }
+
+    pub fn map(&self) -> Map {
+        Map {
+            tiles: Grid::new(self.width, self.height, self.tiles.clone()),
+        }
+    }
+}
+
+/// Called with the seats of every round of a [`Seating`] simulation.
+pub type Observer<'a> = dyn FnMut(&Seating) -> Result<()> + 'a;
+
+/// How a [`Seating`] simulation ended.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    /// Nothing changes anymore after this many rounds.
+    Converged { rounds: usize },
+    /// The seats of round `start` come back every `period` rounds.
+    Cycle { start: usize, period: usize },
+    /// Neither happened in this many rounds.
+    Capped { rounds: usize },
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Converged { rounds } => write!(f, "converged after {} rounds", rounds),
+            Outcome::Cycle { start, period } => {
+                write!(f, "entered cycle of period {} at round {}", period, start)
+            }
+            Outcome::Capped { rounds } => write!(f, "still changing after {} rounds", rounds),
+        }
+    }
+}
+
+/// Number of occupied seats once `rule` doesn't change them anymore.
+pub fn solve(map: &Map, rule: &dyn SeatingRule) -> Result<usize> {
+    let mut seating = Seating::new(map, rule);
+    match seating.run(None) {
+        Outcome::Converged { .. } => Ok(seating.count(Tile::Occupied)),
+        _ => Err(Error::NoSolution),
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Map;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input, mode)?)
+    }
+
+    fn part1(map: &Self::Input) -> Result<String> {
+        Ok(solve(map, &Strategy::Close)?.to_string())
+    }
+
+    fn part2(map: &Self::Input) -> Result<String> {
+        Ok(solve(map, &Strategy::Visible)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "\
+L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL
+";
+
+    #[test]
+    fn first_round_fills_every_seat() {
+        let map = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let seats = map.count(Tile::Empty);
+        let map = map.round(&Strategy::Close);
+        assert_eq!(map.count(Tile::Occupied), seats);
+        assert_eq!(map.count(Tile::Empty), 0);
+    }
+
+    #[test]
+    fn unknown_tiles() {
+        let err = parse_input("L.L\nLxL\n", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let map = parse_input("L.L\nLxL\n", Mode::Lenient).unwrap();
+        assert_eq!(map.tiles()[(1, 1)], Tile::Floor);
+    }
+
+    #[test]
+    fn visible_neighbors() {
+        let map = parse_input(
+            ".......#.\n...#.....\n.#.......\n.........\n..#L....#\n....#....\n.........\n#........\n...#.....\n", Mode::Strict)
+        .unwrap();
+        assert_eq!(map.occupied_neighbors(&Strategy::Visible, (3, 4)), 8);
+
+        let map = parse_input(
+            ".............\n.L.L.#.#.#.#.\n.............\n",
+            Mode::Strict,
+        )
+        .unwrap();
+        assert_eq!(map.occupied_neighbors(&Strategy::Visible, (1, 1)), 0);
+        assert_eq!(map.occupied_neighbors(&Strategy::Close, (4, 1)), 1);
+    }
+
+    /// Layout of `size`x`size` tiles, about a quarter of them floor.
+    fn generated(size: usize) -> Map {
+        let mut state = 12345u64;
+        let mut input = String::new();
+        for _ in 0..size {
+            for _ in 0..size {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                input.push(if state >> 62 == 0 { '.' } else { 'L' });
+            }
+            input.push('\n');
+        }
+        parse_input(&input, Mode::Strict).unwrap()
+    }
+
+    #[test]
+    fn seating_counts_changes() {
+        let map = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let mut seating = Seating::new(&map, &Strategy::Close);
+        assert_eq!(seating.step(), map.count(Tile::Empty));
+        let mut rounds = 1;
+        while seating.step() > 0 {
+            rounds += 1;
+        }
+        assert_eq!((rounds, seating.rounds()), (5, 6));
+        assert_eq!(seating.count(Tile::Occupied), 37);
+        assert_eq!(seating.map().round(&Strategy::Close), seating.map());
+    }
+
+    #[test]
+    fn outcomes() {
+        let map = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let mut seating = Seating::new(&map, &Strategy::Close);
+        let outcome = seating.run(None);
+        assert_eq!(outcome, Outcome::Converged { rounds: 5 });
+        assert_eq!(outcome.to_string(), "converged after 5 rounds");
+        assert_eq!(seating.count(Tile::Occupied), 37);
+
+        let mut seating = Seating::new(&map, &Strategy::Visible);
+        assert_eq!(seating.run(Some(3)), Outcome::Capped { rounds: 3 });
+        assert_eq!(seating.run(Some(10)), Outcome::Converged { rounds: 6 });
+
+        // adjacent seats keep changing on this layout, two rounds at a time
+        let map = generated(60);
+        let mut seating = Seating::new(&map, &Strategy::Close);
+        let outcome = seating.run(None);
+        assert!(
+            matches!(outcome, Outcome::Cycle { period: 2, .. }),
+            "{}",
+            outcome
+        );
+        if let Outcome::Cycle { start, .. } = outcome {
+            let settled = Seating::new(&map, &Strategy::Close).run(Some(start));
+            assert_eq!(settled, Outcome::Capped { rounds: start });
+        }
+        assert_eq!(
+            Outcome::Cycle {
+                start: 4,
+                period: 2
+            }
+            .to_string(),
+            "entered cycle of period 2 at round 4"
+        );
+    }
+
+    #[test]
+    fn seating_matches_naive_rounds() {
+        // random layouts don't always settle with adjacent seats, so rounds
+        // are compared one by one
+        let map = generated(60);
+        for strategy in &[Strategy::Close, Strategy::Visible] {
+            let mut seating = Seating::new(&map, strategy);
+            let mut naive = map.clone();
+            for _ in 0..60 {
+                let mut next = naive.clone();
+                for pos in naive.tiles.positions() {
+                    let occupied = naive.occupied_neighbors(strategy, pos);
+                    next.tiles[pos] = match naive.tiles[pos] {
+                        Tile::Empty if occupied == 0 => Tile::Occupied,
+                        Tile::Occupied if occupied >= strategy.tolerance() => Tile::Empty,
+                        tile => tile,
+                    };
+                }
+                let changed = next
+                    .tiles
+                    .iter()
+                    .zip(naive.tiles.iter())
+                    .filter(|(a, b)| a != b);
+                assert_eq!(seating.step(), changed.count());
+                assert_eq!(seating.map(), next);
+                naive = next;
+            }
+            let initial: Vec<_> = map.tiles.iter().copied().collect();
+            assert_eq!(seating.replay(&initial, 60), seating.tiles);
+        }
+        assert_eq!(solve(&map, &Strategy::Visible).unwrap(), 808);
+    }
+
+    #[test]
+    fn neighborhoods() {
+        let map = parse_input("#####\n##.##\n#.L.#\n##.##\n#####\n", Mode::Strict).unwrap();
+        let center = (2, 2);
+        let count = |neighborhood: &str| {
+            let rule = Rule {
+                neighborhood: neighborhood.parse().unwrap(),
+                boundary: Boundary::Wall,
+                birth: 0,
+                tolerance: 4,
+            };
+            map.occupied_neighbors(&rule, center)
+        };
+        assert_eq!(count("adjacent"), 4);
+        assert_eq!(count("sight"), 8);
+        assert_eq!(count("radius:1"), 4);
+        assert_eq!(count("radius:2"), 20);
+        assert_eq!(count("manhattan:1"), 0);
+        assert_eq!(count("manhattan:2"), 8);
+        assert_eq!(count("radius:0"), 0);
+        assert!("radius".parse::<Neighborhood>().is_err());
+        assert!("sight:2".parse::<Neighborhood>().is_err());
+    }
+
+    #[test]
+    fn boundaries() {
+        let rule = |neighborhood: &str, boundary: &str| Rule {
+            neighborhood: neighborhood.parse().unwrap(),
+            boundary: boundary.parse().unwrap(),
+            ..Strategy::Close.rule()
+        };
+        let full = parse_input("####\n####\n####\n####\n", Mode::Strict).unwrap();
+        let corner =
+            |neighborhood, boundary| full.occupied_neighbors(&rule(neighborhood, boundary), (0, 0));
+        assert_eq!(corner("adjacent", "wall"), 3);
+        assert_eq!(corner("adjacent", "torus"), 8);
+        // the mirrored neighbors are the same three seats, some of them counted twice
+        assert_eq!(corner("adjacent", "reflect"), 8);
+        assert_eq!(
+            full.tiles()
+                .positions()
+                .map(|pos| rule("adjacent", "reflect").neighbors(&full, pos).len())
+                .min(),
+            Some(8)
+        );
+        assert_eq!(corner("manhattan:2", "torus"), 12);
+
+        let row = parse_input("L...#.\n", Mode::Strict).unwrap();
+        let sight = |boundary, pos, dir| {
+            let boundary: Boundary = str::parse(boundary).unwrap();
+            boundary.sight(row.tiles(), pos, dir)
+        };
+        assert_eq!(sight("wall", (0, 0), (-1, 0)), None);
+        assert_eq!(sight("torus", (0, 0), (-1, 0)), Some((4, 0)));
+        assert_eq!(sight("reflect", (0, 0), (-1, 0)), Some((4, 0)));
+        // the mirror right of it only shows the seat itself
+        assert_eq!(sight("reflect", (4, 0), (1, 0)), None);
+        assert_eq!(sight("torus", (4, 0), (1, 0)), Some((0, 0)));
+        // rays coming back to their seat see nothing, also on a single row
+        let lonely = parse_input("..L...\n", Mode::Strict).unwrap();
+        for &boundary in &[Boundary::Torus, Boundary::Reflect] {
+            for &dir in SURROUNDING.iter() {
+                assert_eq!(boundary.sight(lonely.tiles(), (2, 0), dir), None);
+            }
+        }
+
+        assert_eq!(Boundary::Reflect.fold(-1, 4), Some(1));
+        assert_eq!(Boundary::Reflect.fold(5, 4), Some(1));
+        assert_eq!(Boundary::Reflect.fold(7, 4), Some(1));
+        assert_eq!(Boundary::Reflect.fold(-3, 1), Some(0));
+        assert_eq!(Boundary::Torus.fold(-1, 4), Some(3));
+        assert_eq!(Boundary::Wall.fold(4, 4), None);
+        assert!("mirror".parse::<Boundary>().is_err());
+    }
+
+    #[test]
+    fn boundaries_change_the_outcome() {
+        let map = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        for &boundary in &[Boundary::Torus, Boundary::Reflect] {
+            let rule = Rule {
+                boundary,
+                ..Strategy::Close.rule()
+            };
+            let mut seating = Seating::new(&map, &rule);
+            let outcome = seating.run(Some(1000));
+            assert_ne!(outcome, Outcome::Capped { rounds: 1000 });
+            let mut wall = Seating::new(&map, &Strategy::Close);
+            wall.run(None);
+            assert_ne!(seating.map(), wall.map(), "{:?}", boundary);
+        }
+    }
+
+    /// Seats only care about the seats left and right of them.
+    struct Bench;
+
+    impl SeatingRule for Bench {
+        fn neighbors(&self, map: &Map, pos: (usize, usize)) -> Vec<(usize, usize)> {
+            map.tiles().neighbors(pos, &[(-1, 0), (1, 0)]).collect()
+        }
+
+        fn birth(&self, occupied: usize) -> bool {
+            occupied < 2
+        }
+
+        fn tolerance(&self) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn custom_rules() {
+        let map = parse_input("LLLL\nL.LL\n", Mode::Strict).unwrap();
+        let mut seating = Seating::new(&map, &Bench);
+        seating.step();
+        assert_eq!(seating.count(Tile::Occupied), 7);
+        seating.step();
+        let map = seating.map();
+        assert_eq!(map.tiles().to_string(), "#LL#\n#.##\n");
+        // the middle of the first row never settles
+        seating.step();
+        seating.step();
+        assert_eq!(seating.map(), map);
+        let mut seating = Seating::new(&map, &Bench);
+        assert_eq!(
+            seating.run(None),
+            Outcome::Cycle {
+                start: 0,
+                period: 2
+            }
+        );
+        assert!(solve(&map, &Bench).is_err());
+
+        let same = Rule {
+            tolerance: 5,
+            ..Strategy::Visible.rule()
+        };
+        let map = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        assert_eq!(solve(&map, &same).unwrap(), 26);
+        let lenient = Rule { birth: 1, ..same };
+        // seats are only taken again in the third round
+        let rounds = |rule: &Rule| {
+            let mut seating = Seating::new(&map, rule);
+            for _ in 0..3 {
+                seating.step();
+            }
+            seating.map()
+        };
+        let (strict, lenient) = (rounds(&same), rounds(&lenient));
+        assert!(lenient.count(Tile::Occupied) > strict.count(Tile::Occupied));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(
+            Day11::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(),
+            "37"
+        );
+        assert_eq!(
+            Day11::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(),
+            "26"
+        );
+    }
+}
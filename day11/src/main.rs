@@ -1,170 +1,145 @@
-use std::{env::args, fs::read_to_string};
+use aoc_common::{print_json, Cli, Format, Opt, Result};
+use day11::{
+    export::{frame, FrameFormat, Replay},
+    Boundary, Day11, Map, Outcome, Rule, Seating, SeatingRule, Strategy, Tile,
+};
+use std::{fs, io, path::PathBuf, time::Duration};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Tile {
-    Occupied,
-    Empty,
-    Floor,
-}
+const USAGE: &str = "\
+usage: day11 [<input>|-] [--neighborhood <adjacent|sight|radius:<k>|manhattan:<k>>]
+             [--tolerance <n>] [--birth <n>] [--boundary <wall|torus|reflect>]
+             [--max-rounds <n>] [--replay <ms>]
+             [--frames <dir>] [--frame-format <png|ppm>] [--scale <n>]
+             [--format <human|json>] [--strict]
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Strategy {
-    Visible,
-    Close,
-}
+Any of --neighborhood, --tolerance or --birth also runs a custom rule, based on
+the first part's: adjacent seats, left from 4 occupied neighbors and taken
+with at most --birth (0) occupied neighbors.
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-struct Map {
-    tiles: Vec<Vec<Tile>>,
-    width: usize,
-    height: usize,
-}
+--boundary sets what every rule finds past the edges: nothing (wall), the
+opposite edge (torus) or the map mirrored (reflect).
 
-impl std::fmt::Display for Map {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "w: {}", self.width)?;
-        writeln!(f, "h: {}", self.height)?;
-        for x in 0..self.height {
-            for y in 0..self.width {
-                write!(
-                    f,
-                    "{}",
-                    match self.tiles[x][y] {
-                        Tile::Empty => 'L',
-                        Tile::Occupied => '#',
-                        Tile::Floor => '.',
-                    }
-                )?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
-    }
-}
+Every rule stops once its seats settle or repeat an earlier round, or after
+--max-rounds rounds.
 
-impl Map {
-    const DIRECTIONS: [(i32, i32); 8] = [
-        (-1, -1),
-        (-1, 0),
-        (-1, 1),
-        (0, -1),
-        (0, 1),
-        (1, -1),
-        (1, 0),
-        (1, 1),
-    ];
+--replay draws every round in the terminal, <ms> apart. --frames writes every
+round to <dir>/<rule>-<round>.<png|ppm>, every tile <scale> (4) pixels wide.";
 
-    pub fn round(&self, strategy: Strategy) -> Map {
-        let mut new_map = self.clone();
-        let (neighbors_f, threshold): (&dyn Fn(&Map, usize, usize) -> usize, usize) = match strategy
-        {
-            Strategy::Close => (&Map::count_neighbors, 4),
-            Strategy::Visible => (&Map::count_visible_neighbors, 5),
+const OPTIONS: &[Opt] = &[
+    Opt::value("--neighborhood"),
+    Opt::value("--tolerance"),
+    Opt::value("--birth"),
+    Opt::value("--boundary"),
+    Opt::value("--max-rounds"),
+    Opt::value("--replay"),
+    Opt::value("--frames"),
+    Opt::value("--frame-format").any_format(),
+    Opt::value("--scale").any_format(),
+];
+
+struct Options {
+    rule: Option<Rule>,
+    boundary: Boundary,
+    max_rounds: Option<usize>,
+    replay: Option<Duration>,
+    frames: Option<PathBuf>,
+    frame_format: FrameFormat,
+    scale: usize,
+}
+
+impl Options {
+    fn from_cli(cli: &Cli) -> Result<Options> {
+        let boundary = cli
+            .value("--boundary")
+            .map_or(Ok(Boundary::Wall), str::parse)?;
+        let mut rule = None;
+        let custom = || Rule {
+            boundary,
+            ..Strategy::Close.rule()
         };
-        for x in 0..self.height {
-            for y in 0..self.width {
-                if let Tile::Empty | Tile::Occupied = self.tiles[x][y] {
-                    let cnt = neighbors_f(&self, x, y);
-                    match cnt {
-                        0 => new_map.tiles[x][y] = Tile::Occupied,
-                        n if n >= threshold => new_map.tiles[x][y] = Tile::Empty,
-                        _ => (),
-                    }
-                }
-            }
+        if let Some(neighborhood) = cli.value("--neighborhood") {
+            rule.get_or_insert_with(custom).neighborhood = neighborhood.parse()?;
         }
-        new_map
-    }
-    fn count_visible_neighbors(&self, x: usize, y: usize) -> usize {
-        let mut count = 0;
-        for &(xo, yo) in Map::DIRECTIONS.iter() {
-            let (mut x, mut y) = (x as i32, y as i32);
-            loop {
-                x += xo;
-                y += yo;
-                match self
-                    .tiles
-                    .get(x as usize)
-                    .map(|row| row.get(y as usize))
-                    .flatten()
-                {
-                    Some(Tile::Occupied) => {
-                        count += 1;
-                        break;
-                    }
-                    None | Some(Tile::Empty) => break,
-                    _ => (),
-                }
-            }
+        if let Some(tolerance) = cli.value("--tolerance") {
+            rule.get_or_insert_with(custom).tolerance = tolerance.parse()?;
         }
-        count
-    }
-
-    fn count_neighbors(&self, x: usize, y: usize) -> usize {
-        let (x, y) = (x as i32, y as i32);
-        let mut count = 0;
-        for &(xo, yo) in Map::DIRECTIONS.iter() {
-            if xo == 0 && yo == 0 || x + xo < 0 || y + yo < 0 {
-                continue;
-            }
-            if std::matches!(
-                self.tiles
-                    .get((x + xo) as usize)
-                    .map(|row| row.get((y + yo) as usize)),
-                Some(Some(Tile::Occupied))
-            ) {
-                count += 1;
-            }
+        if let Some(birth) = cli.value("--birth") {
+            rule.get_or_insert_with(custom).birth = birth.parse()?;
         }
-        count
-    }
-
-    pub fn count(&self, tile: Tile) -> usize {
-        self.tiles
-            .iter()
-            .map(|row| row.iter().filter(|&&t| t == tile).count())
-            .sum()
-    }
-}
-
-fn parse_input(input: &str) -> Map {
-    Map {
-        tiles: input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| match c {
-                        'L' => Tile::Empty,
-                        '#' => Tile::Occupied,
-                        _ => Tile::Floor,
-                    })
-                    .collect()
-            })
-            .collect(),
-        width: input.lines().next().map(|l| l.chars().count()).unwrap_or(0),
-        height: input.lines().count(),
+        Ok(Options {
+            rule,
+            boundary,
+            max_rounds: cli.value("--max-rounds").map(str::parse).transpose()?,
+            replay: cli
+                .value("--replay")
+                .map(str::parse)
+                .transpose()?
+                .map(Duration::from_millis),
+            frames: cli.value("--frames").map(PathBuf::from),
+            frame_format: cli
+                .value("--frame-format")
+                .map_or(Ok(FrameFormat::Png), str::parse)?,
+            scale: cli.value("--scale").map_or(Ok(4), str::parse)?,
+        })
     }
 }
 
-fn solve(map: &Map, strategy: Strategy) -> usize {
-    let mut map = map.clone();
-    loop {
-        let new_map = map.round(strategy);
-        if new_map == map {
-            break map.count(Tile::Occupied);
+/// Prints the occupied seats once `rule` settles, or how it didn't, replaying
+/// and exporting the rounds as asked by `options`.
+fn report(name: &str, map: &Map, rule: &dyn SeatingRule, options: &Options) -> Result<()> {
+    let mut replay = options.replay.map(|delay| Replay::new(io::stdout(), delay));
+    let mut observe = |seating: &Seating| -> Result<()> {
+        if replay.is_none() && options.frames.is_none() {
+            return Ok(());
         }
-        map = new_map;
+        let map = seating.map();
+        if let Some(replay) = &mut replay {
+            replay.frame(seating.rounds(), &map)?;
+        }
+        if let Some(dir) = &options.frames {
+            let format = options.frame_format;
+            let file = format!("{}-{:04}.{}", name, seating.rounds(), format.extension());
+            fs::write(dir.join(file), format.encode(&frame(&map, options.scale))?)?;
+        }
+        Ok(())
+    };
+    let mut seating = Seating::new(map, rule);
+    match seating.run_observed(options.max_rounds, &mut observe)? {
+        Outcome::Converged { .. } => println!("{}: {}", name, seating.count(Tile::Occupied)),
+        outcome => println!(
+            "{}: {}, {} seats occupied in round {}",
+            name,
+            outcome,
+            seating.count(Tile::Occupied),
+            seating.rounds()
+        ),
     }
+    Ok(())
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input = read_to_string(args().nth(1).ok_or("No input file")?)?;
-    let map = parse_input(&input);
+    let cli = Cli::from_env_with(USAGE, OPTIONS)?;
+    let input = cli.input(11)?;
+    if cli.format == Format::Json {
+        print_json::<Day11>(11, &input, cli.mode);
+        return Ok(());
+    }
+    let options = Options::from_cli(&cli)?;
+    let map = input.parse::<Day11>(cli.mode)?;
 
-    let part1 = solve(&map, Strategy::Close);
-    let part2 = solve(&map, Strategy::Visible);
-    println!("part1: {}", part1);
-    println!("part2: {}", part2);
+    if let Some(dir) = &options.frames {
+        fs::create_dir_all(dir)?;
+    }
+    for &(name, strategy) in &[("part1", Strategy::Close), ("part2", Strategy::Visible)] {
+        let rule = Rule {
+            boundary: options.boundary,
+            ..strategy.rule()
+        };
+        report(name, &map, &rule, &options)?;
+    }
+    if let Some(rule) = &options.rule {
+        report("custom", &map, rule, &options)?;
+    }
 
     Ok(())
 }
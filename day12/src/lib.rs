@@ -0,0 +1,225 @@
+use aoc_common::{collect, Diagnostic, Mode, Result, Solution, Source};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Position {
+    x: i32,
+    y: i32,
+}
+
+impl std::ops::AddAssign for Position {
+    fn add_assign(&mut self, rhs: Position) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl Position {
+    fn scal_mult(&self, v: i32) -> Position {
+        Position {
+            x: self.x * v,
+            y: self.y * v,
+        }
+    }
+    /// Rotates clockwise by `val` degrees, rounded down to quarter turns.
+    fn rotate(&self, val: i32) -> Position {
+        match val.rem_euclid(360) / 90 {
+            1 => Position {
+                x: -self.y,
+                y: self.x,
+            },
+            2 => Position {
+                x: -self.x,
+                y: -self.y,
+            },
+            3 => Position {
+                x: self.y,
+                y: -self.x,
+            },
+            _ => *self,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Rotates clockwise by `val` degrees, rounded down to quarter turns.
+    fn rotate(&self, val: i32) -> Direction {
+        (0..val.rem_euclid(360) / 90).fold(*self, |direction, _| match direction {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Instruction {
+    Forward(i32),
+    /// Clockwise degrees, a multiple of 90.
+    Rotate(i32),
+    Direction(Direction, i32),
+}
+
+fn parse_instruction<'a>(source: &Source<'a>, line: &'a str) -> Result<Instruction, Diagnostic> {
+    let (ins, val) = line.split_at(line.chars().next().map_or(0, char::len_utf8));
+    if ins.is_empty() || !"NESWFRL".contains(ins) {
+        return Err(source.error(ins, "one of \"NESWFRL\""));
+    }
+    let val = source
+        .parse::<i32>(val, "number")
+        .and_then(|num| match ins {
+            "R" | "L" if num % 90 != 0 => Err(source.error(val, "multiple of 90")),
+            _ => Ok(num),
+        })?;
+    Ok(match ins {
+        "N" => Instruction::Direction(Direction::North, val),
+        "E" => Instruction::Direction(Direction::East, val),
+        "S" => Instruction::Direction(Direction::South, val),
+        "W" => Instruction::Direction(Direction::West, val),
+        "R" => Instruction::Rotate(val),
+        "L" => Instruction::Rotate(-val),
+        _ => Instruction::Forward(val),
+    })
+}
+
+pub fn parse_input(input: &str, mode: Mode) -> Result<Vec<Instruction>, Diagnostic> {
+    let source = Source::new(input);
+    collect(input.lines().map(|l| parse_instruction(&source, l)), mode)
+}
+
+pub fn dir_len_to_vec(length: i32, rotation: Direction) -> Position {
+    match rotation {
+        Direction::North => Position { x: length, y: 0 },
+        Direction::East => Position { x: 0, y: length },
+        Direction::South => Position { x: -length, y: 0 },
+        Direction::West => Position { x: 0, y: -length },
+    }
+}
+
+pub fn run_ship1(
+    start: Position,
+    rotation: Direction,
+    instructions: &[Instruction],
+) -> (Position, Direction) {
+    instructions
+        .iter()
+        .fold((start, rotation), |(mut pos, mut rot), ins| {
+            match ins {
+                Instruction::Forward(v) => pos += dir_len_to_vec(*v, rot),
+                Instruction::Rotate(v) => rot = rot.rotate(*v),
+                Instruction::Direction(Direction::North, val) => pos.x += val,
+                Instruction::Direction(Direction::East, val) => pos.y += val,
+                Instruction::Direction(Direction::South, val) => pos.x -= val,
+                Instruction::Direction(Direction::West, val) => pos.y -= val,
+            };
+            (pos, rot)
+        })
+}
+
+pub fn run_ship2(
+    start: Position,
+    waypoint: Position,
+    instructions: &[Instruction],
+) -> (Position, Position) {
+    instructions
+        .iter()
+        .fold((start, waypoint), |(mut pos, mut w), ins| {
+            match ins {
+                Instruction::Forward(v) => pos += w.scal_mult(*v),
+                Instruction::Rotate(v) => w = w.rotate(*v),
+                Instruction::Direction(Direction::North, val) => w.x += val,
+                Instruction::Direction(Direction::East, val) => w.y += val,
+                Instruction::Direction(Direction::South, val) => w.x -= val,
+                Instruction::Direction(Direction::West, val) => w.y -= val,
+            };
+            (pos, w)
+        })
+}
+
+pub fn part1(instructions: &[Instruction]) -> i32 {
+    let start = Position { x: 0, y: 0 };
+    let end = run_ship1(start, Direction::East, instructions);
+    end.0.x.abs() + end.0.y.abs()
+}
+
+pub fn part2(instructions: &[Instruction]) -> i32 {
+    let start = Position { x: 0, y: 0 };
+    let end = run_ship2(start, Position { x: 1, y: 10 }, instructions);
+    end.0.x.abs() + end.0.y.abs()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input, mode)?)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<String> {
+        Ok(part1(instructions).to_string())
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<String> {
+        Ok(part2(instructions).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "F10\nN3\nF7\nR90\nF11\n";
+
+    #[test]
+    fn rotations() {
+        assert_eq!(Direction::East.rotate(90), Direction::South);
+        assert_eq!(Direction::East.rotate(-90), Direction::North);
+        assert_eq!(Direction::North.rotate(270), Direction::West);
+        let waypoint = Position { x: 4, y: 10 };
+        assert_eq!(waypoint.rotate(90), Position { x: -10, y: 4 });
+        assert_eq!(waypoint.rotate(-90), Position { x: 10, y: -4 });
+    }
+
+    #[test]
+    fn invalid_instruction() {
+        assert!(parse_input("X10", Mode::Strict).is_err());
+        assert!(parse_input("Fab", Mode::Strict).is_err());
+
+        let input = "F10\n\nR45\nL-90\n";
+        let err = parse_input(input, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.expected, "one of \"NESWFRL\"");
+        let err = parse_input("R45\n", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+        assert_eq!(err.expected, "multiple of 90");
+
+        let instructions = parse_input(input, Mode::Lenient).unwrap();
+        assert_eq!(
+            instructions,
+            vec![Instruction::Forward(10), Instruction::Rotate(90)]
+        );
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(
+            Day12::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(),
+            "25"
+        );
+        assert_eq!(
+            Day12::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(),
+            "286"
+        );
+    }
+}
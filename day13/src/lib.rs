@@ -0,0 +1,170 @@
+use aoc_common::{Diagnostic, Error, Mode, Result, Solution, Source};
+
+/// The arrival and bus lines of the notes.
+fn notes(input: &str) -> Result<(&str, &str), Diagnostic> {
+    let source = Source::new(input);
+    let end = &input[input.len()..];
+    let mut lines = input.lines();
+    let arrival = lines
+        .next()
+        .ok_or_else(|| source.error(end, "arrival time"))?;
+    let buses = lines.next().ok_or_else(|| source.error(end, "bus ids"))?;
+    Ok((arrival, buses))
+}
+
+pub fn parse_bus_ids(input: &str) -> Result<Vec<i32>, std::num::ParseIntError> {
+    input
+        .split(',')
+        .filter(|&v| v != "x")
+        .map(str::parse::<i32>)
+        .collect()
+}
+
+pub fn part1(input: &str) -> Result<i32> {
+    let (arrival, buses) = notes(input)?;
+    let arrival = arrival.parse::<i32>()?;
+    let bus_ids = parse_bus_ids(buses)?;
+    let (wait, id) = bus_ids
+        .iter()
+        .map(|v| {
+            let m = arrival % v;
+            if m != 0 {
+                (v - m, v)
+            } else {
+                (m, v)
+            }
+        })
+        .min_by(|(v1, _), (v2, _)| v1.cmp(v2))
+        .ok_or(Error::NoSolution)?;
+    Ok(wait * id)
+}
+
+pub fn parse_bus_ids2(input: &str) -> Result<Vec<(i64, i64)>, std::num::ParseIntError> {
+    input
+        .split(',')
+        .enumerate()
+        .filter(|&(_, v)| v != "x")
+        .map(|(idx, v)| {
+            let v = str::parse::<i64>(v)?;
+            Ok((v, v - idx as i64))
+        })
+        .collect()
+}
+
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if a == 0 {
+        (b, 0, 1)
+    } else {
+        let (g, x, y) = egcd(b % a, a);
+        (g, y - (b / a) * x, x)
+    }
+}
+
+pub fn part2(input: &str) -> Result<i64> {
+    let (_, buses) = notes(input)?;
+    let bus_ids = parse_bus_ids2(buses)?;
+    if bus_ids.is_empty() {
+        return Err(Error::NoSolution);
+    }
+    let remainders: Vec<_> = bus_ids.iter().map(|(_, r)| r).collect();
+
+    let prod: i64 = bus_ids.iter().map(|(v, _)| v).product();
+
+    let inv = bus_ids
+        .iter()
+        .map(|(v, _)| {
+            let (_, _, mut i) = egcd(*v, prod / v);
+            if i < 0 {
+                i += v;
+            }
+            i * (prod / v)
+        })
+        .collect::<Vec<_>>();
+
+    let res: i64 = remainders
+        .iter()
+        .zip(inv.iter())
+        .map(|(&r, &i)| r * i)
+        .sum();
+    Ok(res % prod)
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = String;
+
+    fn parse(input: &str, _mode: Mode) -> Result<Self::Input> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> Result<String> {
+        Ok(part1(input)?.to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String> {
+        Ok(part2(input)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "939\n7,13,x,x,59,x,31,19\n";
+
+    #[test]
+    fn earliest_bus() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 295);
+    }
+
+    #[test]
+    fn earliest_timestamp() {
+        let cases = [
+            ("7,13,x,x,59,x,31,19", 1068781),
+            ("17,x,13,19", 3417),
+            ("67,7,59,61", 754018),
+            ("67,x,7,59,61", 779210),
+            ("67,7,x,59,61", 1261476),
+            ("1789,37,47,1889", 1202161486),
+        ];
+        for &(buses, expected) in cases.iter() {
+            assert_eq!(
+                part2(&format!("0\n{}\n", buses)).unwrap(),
+                expected,
+                "{}",
+                buses
+            );
+        }
+    }
+
+    #[test]
+    fn missing_buses() {
+        match part1("939\n") {
+            Err(Error::Diagnostic(d)) => assert_eq!((d.line, d.expected.as_str()), (2, "bus ids")),
+            other => panic!("expected a diagnostic, got {:?}", other),
+        }
+        assert!(matches!(part1(""), Err(Error::Diagnostic(_))));
+        assert!(matches!(part2("939\n"), Err(Error::Diagnostic(_))));
+        assert!(matches!(part1("939\nx,x\n"), Err(Error::NoSolution)));
+        assert!(matches!(part2("939\nx\n"), Err(Error::NoSolution)));
+    }
+
+    #[test]
+    fn extended_gcd() {
+        assert_eq!(egcd(240, 46), (2, -9, 47));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(
+            Day13::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(),
+            "295"
+        );
+        assert_eq!(
+            Day13::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(),
+            "1068781"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use aoc_common::{Diagnostic, Mode, Result, Solution, Source};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub enum Instruction {
+    Memory(i64, i64),
+    Mask(Vec<char>),
+}
+
+const MASK_LEN: usize = 36;
+
+fn parse_instruction<'a>(source: &Source<'a>, line: &'a str) -> Result<Instruction, Diagnostic> {
+    let (ins, val) = source.split(line, " = ")?;
+    if ins == "mask" {
+        if val.len() != MASK_LEN || !val.chars().all(|c| matches!(c, '0' | '1' | 'X')) {
+            return Err(source.error(val, "36 bit mask of \"0\", \"1\" and \"X\""));
+        }
+        return Ok(Instruction::Mask(val.chars().rev().collect()));
+    }
+    let addr = ins
+        .strip_prefix("mem[")
+        .and_then(|addr| addr.strip_suffix(']'))
+        .ok_or_else(|| source.error(ins, "\"mask\" or \"mem[<address>]\""))?;
+    Ok(Instruction::Memory(
+        source.parse(addr, "address")?,
+        source.parse(val, "number")?,
+    ))
+}
+
+/// Parses the program, lines without an assignment are skipped unless `mode` is strict.
+pub fn parse_input(input: &str, mode: Mode) -> Result<Vec<Instruction>, Diagnostic> {
+    let source = Source::new(input);
+    input
+        .lines()
+        .filter(|l| mode == Mode::Strict || l.contains(" = "))
+        .map(|l| parse_instruction(&source, l))
+        .collect()
+}
+
+pub fn run1(memory: &mut HashMap<i64, i64>, instructions: &[Instruction]) {
+    let mut mask = Vec::new();
+    for ins in instructions {
+        match ins {
+            Instruction::Memory(a, v) => {
+                let mut val = *v;
+                mask.iter().enumerate().for_each(|(idx, v)| match v {
+                    '0' => val &= !(1 << idx),
+                    '1' => val |= 1 << idx,
+                    _ => (),
+                });
+                memory.insert(*a, val);
+            }
+            Instruction::Mask(v) => mask = v.clone(),
+        }
+    }
+}
+
+pub fn generate_addr(addr: &[char], pos: usize, mut new_addr: String, adressess: &mut Vec<String>) {
+    if pos >= addr.len() {
+        adressess.push(new_addr);
+        return;
+    }
+    match addr[pos] {
+        'X' => {
+            let mut m1 = new_addr.clone();
+            let mut m2 = new_addr;
+            m1.push('1');
+            m2.push('0');
+            generate_addr(addr, pos + 1, m1, adressess);
+            generate_addr(addr, pos + 1, m2, adressess);
+        }
+        c => {
+            new_addr.push(c);
+            generate_addr(addr, pos + 1, new_addr, adressess);
+        }
+    }
+}
+
+pub fn run2(memory: &mut HashMap<i64, i64>, instructions: &[Instruction]) {
+    let mut mask = Vec::new();
+    for ins in instructions {
+        match ins {
+            Instruction::Memory(a, v) => {
+                let mut addr = Vec::new();
+                for i in (0..MASK_LEN).rev() {
+                    let c = match a & 1 << i {
+                        0 => '0',
+                        _ => '1',
+                    };
+                    // without a mask every address bit is kept
+                    match mask.get(i).unwrap_or(&'0') {
+                        '0' => addr.push(c),
+                        '1' => addr.push('1'),
+                        _ => addr.push('X'),
+                    }
+                }
+                let mut addresses = Vec::new();
+                generate_addr(&addr, 0, String::new(), &mut addresses);
+                for addr in addresses {
+                    let a = addr.bytes().fold(0, |acc, v| match v {
+                        b'0' => acc << 1,
+                        _ => acc << 1 | 1,
+                    });
+                    memory.insert(a, *v);
+                }
+            }
+            Instruction::Mask(v) => {
+                mask = v.clone();
+            }
+        }
+    }
+}
+
+pub fn part1(instructions: &[Instruction]) -> i64 {
+    let mut memory = HashMap::new();
+    run1(&mut memory, instructions);
+    memory.values().sum()
+}
+
+pub fn part2(instructions: &[Instruction]) -> i64 {
+    let mut memory = HashMap::new();
+    run2(&mut memory, instructions);
+    memory.values().sum()
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input, mode)?)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<String> {
+        Ok(part1(instructions).to_string())
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<String> {
+        Ok(part2(instructions).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const VALUE_MASK: &str = "\
+mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0
+";
+
+    const ADDRESS_MASK: &str = "\
+mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1
+";
+
+    #[test]
+    fn value_mask() {
+        assert_eq!(
+            Day14::solve(VALUE_MASK, Part::One, Mode::Strict).unwrap(),
+            "165"
+        );
+    }
+
+    #[test]
+    fn floating_addresses() {
+        let mut addresses = Vec::new();
+        generate_addr(&['X', '1', 'X'], 0, String::new(), &mut addresses);
+        assert_eq!(addresses, vec!["111", "110", "011", "010"]);
+    }
+
+    #[test]
+    fn address_mask() {
+        assert_eq!(
+            Day14::solve(ADDRESS_MASK, Part::Two, Mode::Strict).unwrap(),
+            "208"
+        );
+    }
+
+    #[test]
+    fn malformed_instructions() {
+        let input = "mem[8] = 11\n\nmem[7] = 101\n";
+        assert_eq!(parse_input(input, Mode::Lenient).unwrap().len(), 2);
+        let err = parse_input(input, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = parse_input("mem[x] = 11", Mode::Lenient).unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (5, "address"));
+        let err = parse_input("mem 8 = 11", Mode::Lenient).unwrap_err();
+        assert_eq!(err.column, 1);
+        let err = parse_input("mask = 1X0", Mode::Lenient).unwrap_err();
+        assert_eq!(err.column, 8);
+    }
+}
@@ -0,0 +1,92 @@
+use aoc_common::{Error, Mode, Result, Solution};
+use std::{collections::HashMap, num::ParseIntError};
+
+pub fn parse_input(input: &str) -> impl Iterator<Item = Result<i32, ParseIntError>> + '_ {
+    input.trim().split(',').map(str::parse::<i32>)
+}
+
+pub fn run(start_round: i32, max_round: i32, played: &mut HashMap<i32, i32>, mut n: i32) -> i32 {
+    for current_round in start_round..max_round {
+        if let Some(last_played) = played.get(&n).cloned() {
+            played.insert(n, current_round);
+            n = current_round - last_played;
+        } else {
+            played.insert(n, current_round);
+            n = 0;
+        }
+    }
+    n
+}
+
+/// Returns the number spoken in round `max_round` for the given starting numbers.
+pub fn play(nums: &[i32], max_round: i32) -> Option<i32> {
+    let (&last, rest) = nums.split_last()?;
+    let mut played: HashMap<_, _> = rest
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (*v, i as i32 + 1))
+        .collect();
+    let start_round = nums.len() as i32;
+    Some(run(start_round, max_round, &mut played, last))
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<i32>;
+
+    fn parse(input: &str, _mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input).collect::<Result<_, _>>()?)
+    }
+
+    fn part1(nums: &Self::Input) -> Result<String> {
+        Ok(play(nums, 2020).ok_or(Error::NoSolution)?.to_string())
+    }
+
+    fn part2(nums: &Self::Input) -> Result<String> {
+        Ok(play(nums, 30000000).ok_or(Error::NoSolution)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    #[test]
+    fn first_turns() {
+        let spoken: Vec<_> = (4..=10)
+            .map(|round| play(&[0, 3, 6], round).unwrap())
+            .collect();
+        assert_eq!(spoken, vec![0, 3, 3, 1, 0, 4, 0]);
+    }
+
+    #[test]
+    fn turn_2020() {
+        let cases = [
+            ("0,3,6", 436),
+            ("1,3,2", 1),
+            ("2,1,3", 10),
+            ("1,2,3", 27),
+            ("2,3,1", 78),
+            ("3,2,1", 438),
+            ("3,1,2", 1836),
+        ];
+        for &(start, expected) in cases.iter() {
+            assert_eq!(
+                Day15::solve(start, Part::One, Mode::Strict).unwrap(),
+                expected.to_string()
+            );
+        }
+    }
+
+    // 30M rounds take a while in debug builds
+    #[test]
+    #[ignore]
+    fn turn_30000000() {
+        assert_eq!(
+            Day15::solve("0,3,6", Part::Two, Mode::Strict).unwrap(),
+            "175594"
+        );
+    }
+}
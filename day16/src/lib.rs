@@ -0,0 +1,281 @@
+use aoc_common::{sections, Diagnostic, Error, Mode, Result, Solution, Source};
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    name: String,
+    r1: RangeInclusive<i32>,
+    r2: RangeInclusive<i32>,
+}
+
+pub fn split_input(input: &str) -> Option<(&str, &str, &str)> {
+    let mut split = sections(input);
+    Some((split.next()?, split.next()?, split.next()?))
+}
+
+pub fn parse_range<'a>(
+    source: &Source<'a>,
+    input: &'a str,
+) -> Result<RangeInclusive<i32>, Diagnostic> {
+    let (start, end) = source.split(input, "-")?;
+    Ok(RangeInclusive::new(
+        source.parse(start, "number")?,
+        source.parse(end, "number")?,
+    ))
+}
+
+pub fn parse_rules<'a>(source: &Source<'a>, rules: &'a str) -> Result<Vec<Rule>, Diagnostic> {
+    rules
+        .lines()
+        .map(|line| {
+            let (name, rest) = source.split(line, ": ")?;
+            let (r1, r2) = source.split(rest, " or ")?;
+            Ok(Rule {
+                name: name.to_owned(),
+                r1: parse_range(source, r1)?,
+                r2: parse_range(source, r2)?,
+            })
+        })
+        .collect()
+}
+
+/// Parses tickets below the section header, each has to have `fields` values.
+pub fn parse_tickest<'a>(
+    source: &Source<'a>,
+    input: &'a str,
+    fields: usize,
+) -> Result<Vec<Vec<i32>>, Diagnostic> {
+    input
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let line = line.trim();
+            let ticket = line
+                .split(',')
+                .map(|val| source.parse(val, "number"))
+                .collect::<Result<Vec<_>, _>>()?;
+            if ticket.len() != fields {
+                return Err(source.error(&line[line.len()..], format!("{} values", fields)));
+            }
+            Ok(ticket)
+        })
+        .collect()
+}
+
+pub fn find_invalid<'a>(ticket: &'a [i32], rules: &'a [Rule]) -> impl Iterator<Item = &'a i32> {
+    ticket.iter().filter(move |val| {
+        !rules
+            .iter()
+            .any(|rule| rule.r1.contains(val) || rule.r2.contains(val))
+    })
+}
+
+pub fn part1(nearby: &[Vec<i32>], rules: &[Rule]) -> i32 {
+    nearby.iter().fold(0, |acc, ticket| {
+        acc + find_invalid(ticket, rules).sum::<i32>()
+    })
+}
+
+/// Pairs every rule with the index of the ticket column it describes, `None`
+/// when the columns can't be told apart.
+pub fn assign_rules(nearby: &[Vec<i32>], mut rules: Vec<Rule>) -> Option<Vec<(usize, Rule)>> {
+    let only_valid: Vec<_> = nearby
+        .iter()
+        .filter(|ticket| find_invalid(ticket, &rules).next().is_none())
+        .collect();
+    let columns = only_valid.first().map(|ticket| ticket.len()).unwrap_or(0);
+
+    let mut sorted_rules: Vec<(usize, Rule)> = Vec::new();
+    while !rules.is_empty() {
+        let mut rule_used = false;
+        for idx in 0..columns {
+            if sorted_rules
+                .iter()
+                .any(|&(column_idx, _)| column_idx == idx)
+            {
+                continue;
+            }
+            let mut total = 0;
+            let mut valid = Vec::new();
+            for (ridx, rule) in rules.iter().enumerate() {
+                let n: Vec<_> = only_valid.iter().map(|t| t[idx]).collect();
+                if n.iter()
+                    .all(|val| rule.r1.contains(val) || rule.r2.contains(val))
+                {
+                    total += 1;
+                    valid.push(ridx);
+                }
+            }
+            if total == 1 {
+                sorted_rules.push((idx, rules.remove(valid[0])));
+                rule_used = true;
+            }
+        }
+        if !rule_used {
+            return None;
+        }
+    }
+    Some(sorted_rules)
+}
+
+pub fn part2(nearby: &[Vec<i32>], rules: Vec<Rule>, my_ticket: &[i32]) -> Option<i64> {
+    let sorted_rules = assign_rules(nearby, rules)?;
+    let mut result: i64 = 1;
+    for (column_idx, rule) in sorted_rules {
+        if rule.name.starts_with("departure") {
+            result *= *my_ticket.get(column_idx)? as i64;
+        }
+    }
+    Some(result)
+}
+
+pub struct Notes {
+    pub rules: Vec<Rule>,
+    pub my_ticket: Vec<i32>,
+    pub nearby: Vec<Vec<i32>>,
+}
+
+pub fn parse_input(input: &str) -> Result<Notes> {
+    let source = Source::new(input);
+    let (rules, my, nearby) = split_input(input).ok_or("Missing input section")?;
+    let rules = parse_rules(&source, rules)?;
+    Ok(Notes {
+        my_ticket: parse_tickest(&source, my, rules.len())?
+            .into_iter()
+            .next()
+            .ok_or("Missing ticket")?,
+        nearby: parse_tickest(&source, nearby, rules.len())?,
+        rules,
+    })
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Notes;
+
+    fn parse(input: &str, _mode: Mode) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(notes: &Self::Input) -> Result<String> {
+        Ok(part1(&notes.nearby, &notes.rules).to_string())
+    }
+
+    fn part2(notes: &Self::Input) -> Result<String> {
+        let product = part2(&notes.nearby, notes.rules.clone(), &notes.my_ticket);
+        Ok(product.ok_or(Error::NoSolution)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "\
+class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12
+";
+
+    const FIELDS: &str = "\
+class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9
+";
+
+    #[test]
+    fn error_rate() {
+        let notes = parse_input(EXAMPLE).unwrap();
+        let invalid: Vec<_> = notes
+            .nearby
+            .iter()
+            .flat_map(|ticket| find_invalid(ticket, &notes.rules))
+            .collect();
+        assert_eq!(invalid, vec![&4, &55, &12]);
+        assert_eq!(
+            Day16::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(),
+            "71"
+        );
+    }
+
+    #[test]
+    fn field_order() {
+        let notes = parse_input(FIELDS).unwrap();
+        let mut assigned: Vec<_> = assign_rules(&notes.nearby, notes.rules)
+            .unwrap()
+            .into_iter()
+            .map(|(column, rule)| (column, rule.name))
+            .collect();
+        assigned.sort();
+        assert_eq!(
+            assigned,
+            vec![
+                (0, "row".to_owned()),
+                (1, "class".to_owned()),
+                (2, "seat".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn departure_product() {
+        let input = FIELDS
+            .replace("row:", "departure row:")
+            .replace("seat:", "departure seat:");
+        assert_eq!(
+            Day16::solve(&input, Part::Two, Mode::Strict).unwrap(),
+            (11 * 13).to_string()
+        );
+    }
+
+    #[test]
+    fn malformed_notes() {
+        let err = |input: &str| match parse_input(input) {
+            Err(Error::Diagnostic(d)) => (d.line, d.column, d.expected),
+            _ => panic!("expected a diagnostic"),
+        };
+        assert_eq!(
+            err(&EXAMPLE.replace("1-3", "1..3")),
+            (1, 12, "\"-\"".to_owned())
+        );
+        assert_eq!(
+            err(&EXAMPLE.replace("row:", "row")),
+            (2, 18, "\": \"".to_owned())
+        );
+        assert_eq!(
+            err(&EXAMPLE.replace("7,1,", "7,x,")),
+            (6, 3, "number".to_owned())
+        );
+        assert_eq!(
+            err(&EXAMPLE.replace("40,4,50", "40,4")),
+            (10, 5, "3 values".to_owned())
+        );
+    }
+
+    #[test]
+    fn ambiguous_fields() {
+        let input = FIELDS
+            .replace("class: 0-1 or 4-19", "class: 0-19 or 0-19")
+            .replace("row: 0-5 or 8-19", "row: 0-19 or 0-19");
+        assert!(Day16::solve(&input, Part::Two, Mode::Strict).is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{offsets, rule::Rule, Space};
+use aoc_common::Grid;
+
+/// Cubes of a `dims`-dimensional space stored in a dense box which is fitted
+/// around the active cubes every round.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Dense {
+    /// lowest coordinate of the box in every dimension
+    pub(crate) origin: Vec<i64>,
+    pub(crate) size: Vec<usize>,
+    pub(crate) strides: Vec<usize>,
+    pub(crate) cells: Vec<bool>,
+}
+
+impl Dense {
+    pub(crate) fn empty(origin: Vec<i64>, size: Vec<usize>) -> Self {
+        let strides = size
+            .iter()
+            .scan(1, |stride, &len| {
+                let current = *stride;
+                *stride *= len;
+                Some(current)
+            })
+            .collect();
+        let cells = vec![false; size.iter().product()];
+        Dense {
+            origin,
+            size,
+            strides,
+            cells,
+        }
+    }
+
+    pub(crate) fn index(&self, pos: &[i64]) -> Option<usize> {
+        let mut idx = 0;
+        for (d, &p) in pos.iter().enumerate() {
+            let offset = p - self.origin[d];
+            if offset < 0 || offset as usize >= self.size[d] {
+                return None;
+            }
+            idx += offset as usize * self.strides[d];
+        }
+        Some(idx)
+    }
+
+    fn position(&self, mut idx: usize) -> Vec<i64> {
+        let mut pos = self.origin.clone();
+        for (d, p) in pos.iter_mut().enumerate() {
+            *p += (idx % self.size[d]) as i64;
+            idx /= self.size[d];
+        }
+        pos
+    }
+
+    pub(crate) fn active_positions(&self) -> impl Iterator<Item = Vec<i64>> + '_ {
+        (0..self.cells.len())
+            .filter(move |&idx| self.cells[idx])
+            .map(move |idx| self.position(idx))
+    }
+
+    pub fn count_neighbors(&self, pos: &[i64]) -> usize {
+        offsets(self.dims())
+            .iter()
+            .filter(|offset| offset.iter().any(|&d| d != 0))
+            .filter(|offset| {
+                let neighbor: Vec<_> = pos.iter().zip(offset.iter()).map(|(p, d)| p + d).collect();
+                self.is_active(&neighbor)
+            })
+            .count()
+    }
+}
+
+impl Space for Dense {
+    fn new(seed: &Grid<bool>, dims: usize) -> Self {
+        assert!(dims >= 2, "the seed needs at least two dimensions");
+        let mut size = vec![1; dims];
+        size[0] = seed.width();
+        size[1] = seed.height();
+        let mut space = Dense::empty(vec![0; dims], size);
+        for (x, y) in seed.positions() {
+            let mut pos = vec![0; dims];
+            pos[0] = x as i64;
+            pos[1] = y as i64;
+            let idx = space.index(&pos).unwrap();
+            space.cells[idx] = seed[(x, y)];
+        }
+        space
+    }
+
+    fn dims(&self) -> usize {
+        self.size.len()
+    }
+
+    fn is_active(&self, pos: &[i64]) -> bool {
+        self.index(pos).is_some_and(|idx| self.cells[idx])
+    }
+
+    fn active(&self) -> usize {
+        self.cells.iter().filter(|&&cell| cell).count()
+    }
+
+    fn active_cells(&self) -> Vec<Vec<i64>> {
+        self.active_positions().collect()
+    }
+
+    fn simulate_round(&self, rule: &Rule) -> Dense {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return Dense::empty(self.origin.clone(), vec![0; self.dims()]),
+        };
+        // nothing further than one cube from an active one can become active
+        let origin = min.iter().map(|&min| min - 1).collect();
+        let size = min
+            .iter()
+            .zip(max.iter())
+            .map(|(min, max)| (max - min) as usize + 3)
+            .collect();
+        let mut next = Dense::empty(origin, size);
+
+        let deltas: Vec<isize> = offsets(self.dims())
+            .iter()
+            .map(|offset| {
+                offset
+                    .iter()
+                    .zip(next.strides.iter())
+                    .map(|(&d, &stride)| d as isize * stride as isize)
+                    .sum()
+            })
+            .collect();
+        let mut was_active = vec![false; next.cells.len()];
+        // active cubes count themselves too, which is subtracted below
+        let mut neighbors = vec![0u32; next.cells.len()];
+        for pos in self.active_positions() {
+            let idx = next.index(&pos).unwrap();
+            was_active[idx] = true;
+            for &delta in &deltas {
+                neighbors[(idx as isize + delta) as usize] += 1;
+            }
+        }
+
+        for (idx, cell) in next.cells.iter_mut().enumerate() {
+            let active = was_active[idx];
+            *cell = rule.next(active, (neighbors[idx] - active as u32) as usize);
+        }
+        next
+    }
+}
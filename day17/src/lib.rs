@@ -0,0 +1,358 @@
+use aoc_common::{Error, Grid, Mode, Result, Solution};
+use std::str::FromStr;
+
+pub mod dense;
+pub mod render;
+pub mod rule;
+pub mod sparse;
+pub mod symmetric;
+
+pub use render::{render, Axis, View};
+pub use rule::{Rule, CONWAY};
+
+pub const ROUNDS: usize = 6;
+/// Dimensions of the pocket dimension in the first and the second part.
+pub const DIMS: [usize; 2] = [3, 4];
+
+/// Every offset in `{-1, 0, 1}^dims`, the zero offset included.
+pub fn offsets(dims: usize) -> Vec<Vec<i64>> {
+    (0..dims).fold(vec![Vec::new()], |offsets, _| {
+        offsets
+            .iter()
+            .flat_map(|offset| {
+                (-1..=1).map(move |d| {
+                    let mut offset = offset.clone();
+                    offset.push(d);
+                    offset
+                })
+            })
+            .collect()
+    })
+}
+
+/// Active cubes of a space with any number of dimensions.
+pub trait Space {
+    /// Places the seed into the `x`/`y` plane of a `dims`-dimensional space.
+    fn new(seed: &Grid<bool>, dims: usize) -> Self
+    where
+        Self: Sized;
+    fn dims(&self) -> usize;
+    fn is_active(&self, pos: &[i64]) -> bool;
+    /// Number of active cubes.
+    fn active(&self) -> usize;
+    fn active_cells(&self) -> Vec<Vec<i64>>;
+    fn simulate_round(&self, rule: &Rule) -> Self
+    where
+        Self: Sized;
+
+    /// Lowest and highest coordinates of active cubes, `None` without any.
+    fn bounds(&self) -> Option<(Vec<i64>, Vec<i64>)> {
+        self.active_cells()
+            .into_iter()
+            .fold(None, |bounds, pos| match bounds {
+                None => Some((pos.clone(), pos)),
+                Some((mut min, mut max)) => {
+                    for d in 0..pos.len() {
+                        min[d] = min[d].min(pos[d]);
+                        max[d] = max[d].max(pos[d]);
+                    }
+                    Some((min, max))
+                }
+            })
+    }
+}
+
+/// How the simulated space is stored.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Layout {
+    /// Every cube in a box around the active ones, see [`dense::Dense`].
+    Dense,
+    /// Only the active cubes, see [`sparse::Sparse`].
+    Sparse,
+    /// Only the non-negative half of every extra dimension, see
+    /// [`symmetric::Symmetric`].
+    Symmetric,
+}
+
+impl FromStr for Layout {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "dense" => Ok(Layout::Dense),
+            "sparse" => Ok(Layout::Sparse),
+            "symmetric" => Ok(Layout::Symmetric),
+            _ => Err(format!("Invalid layout: {}", s).into()),
+        }
+    }
+}
+
+/// Called with the number of rounds done and the space, before the first round
+/// and after every one.
+pub type Observer<'a> = dyn FnMut(usize, &dyn Space) -> Result<()> + 'a;
+
+fn run<S: Space>(
+    seed: &Grid<bool>,
+    dims: usize,
+    rounds: usize,
+    rule: &Rule,
+    observe: &mut Observer,
+) -> Result<usize> {
+    let mut space = S::new(seed, dims);
+    observe(0, &space)?;
+    for round in 1..=rounds {
+        space = space.simulate_round(rule);
+        observe(round, &space)?;
+    }
+    Ok(space.active())
+}
+
+/// Like [`solve`], but lets `observe` look at the space after every round.
+pub fn simulate(
+    seed: &Grid<bool>,
+    dims: usize,
+    rounds: usize,
+    rule: &Rule,
+    layout: Layout,
+    observe: &mut Observer,
+) -> Result<usize> {
+    match layout {
+        Layout::Dense => run::<dense::Dense>(seed, dims, rounds, rule, observe),
+        Layout::Sparse => run::<sparse::Sparse>(seed, dims, rounds, rule, observe),
+        Layout::Symmetric => run::<symmetric::Symmetric>(seed, dims, rounds, rule, observe),
+    }
+}
+
+/// Number of active cubes after `rounds` rounds of `rule` in a `dims`-dimensional space.
+pub fn solve(seed: &Grid<bool>, dims: usize, rounds: usize, rule: &Rule, layout: Layout) -> usize {
+    simulate(seed, dims, rounds, rule, layout, &mut |_, _| Ok(())).unwrap()
+}
+
+pub fn parse_input(input: &str, mode: Mode) -> Result<Grid<bool>> {
+    let state = Grid::parse(input, "'#' or '.'", mode, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    Ok(state)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Grid<bool>;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        parse_input(input, mode)
+    }
+
+    fn part1(initial_state: &Self::Input) -> Result<String> {
+        let rule = Rule::default();
+        Ok(solve(initial_state, DIMS[0], ROUNDS, &rule, Layout::Dense).to_string())
+    }
+
+    fn part2(initial_state: &Self::Input) -> Result<String> {
+        let rule = Rule::default();
+        Ok(solve(initial_state, DIMS[1], ROUNDS, &rule, Layout::Dense).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+    use dense::Dense;
+    use sparse::Sparse;
+    use symmetric::Symmetric;
+
+    const EXAMPLE: &str = ".#.\n..#\n###\n";
+
+    #[test]
+    fn invalid_char() {
+        assert!(parse_input(".#.\n.x.\n", Mode::Strict).is_err());
+    }
+
+    #[test]
+    fn first_cycle() {
+        let state = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let life = Rule::default();
+        assert_eq!(solve(&state, 3, 1, &life, Layout::Dense), 11);
+        assert_eq!(solve(&state, 4, 1, &life, Layout::Dense), 29);
+    }
+
+    #[test]
+    fn layouts_agree() {
+        let state = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let life = Rule::default();
+        for dims in 2..=5 {
+            for rounds in 0..4 {
+                let dense = solve(&state, dims, rounds, &life, Layout::Dense);
+                for &layout in &[Layout::Sparse, Layout::Symmetric] {
+                    assert_eq!(
+                        solve(&state, dims, rounds, &life, layout),
+                        dense,
+                        "{:?}, {} dimensions, {} rounds",
+                        layout,
+                        dims,
+                        rounds
+                    );
+                }
+            }
+        }
+        assert_eq!(solve(&state, 3, ROUNDS, &life, Layout::Sparse), 112);
+        assert_eq!(solve(&state, 4, ROUNDS, &life, Layout::Symmetric), 848);
+        assert_eq!("sparse".parse::<Layout>().unwrap(), Layout::Sparse);
+        assert!("hashed".parse::<Layout>().is_err());
+    }
+
+    #[test]
+    fn rulestrings() {
+        let life: Rule = "B3/S23".parse().unwrap();
+        assert_eq!(life, Rule::default());
+        assert!(life.next(false, 3) && life.next(true, 2) && !life.next(true, 4));
+        assert_eq!("b36/s23".parse::<Rule>().unwrap().to_string(), "B36/S23");
+        assert_eq!("B2/S".parse::<Rule>().unwrap().to_string(), "B2/S");
+        let wide: Rule = "B4,13/S0,2".parse().unwrap();
+        assert!(wide.next(false, 13) && !wide.next(false, 3) && wide.next(true, 0));
+        assert_eq!(wide.to_string(), "B4,13/S02");
+        let huge: Rule = format!("B3,{}/S2", usize::MAX).parse().unwrap();
+        assert!(huge.next(false, usize::MAX) && !huge.next(false, 4));
+        for invalid in &["B3S23", "3/23", "B3/Sx", "B03/S23", "B1,x/S"] {
+            assert!(invalid.parse::<Rule>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn other_rules() {
+        let state = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        for rule in &["B36/S23", "B2/S", "B3/S012345678"] {
+            let rule: Rule = rule.parse().unwrap();
+            for dims in 2..=4 {
+                let dense = solve(&state, dims, 3, &rule, Layout::Dense);
+                for &layout in &[Layout::Sparse, Layout::Symmetric] {
+                    assert_eq!(solve(&state, dims, 3, &rule, layout), dense, "{}", rule);
+                }
+            }
+        }
+        // lonely cubes only survive with S0
+        let lonely = parse_input("#..\n...\n..#\n", Mode::Strict).unwrap();
+        let rule: Rule = "B3/S0".parse().unwrap();
+        for &layout in &[Layout::Dense, Layout::Sparse, Layout::Symmetric] {
+            assert_eq!(solve(&lonely, 3, 4, &rule, layout), 2);
+        }
+        // in Seeds every cube dies right after it is born
+        let seeds: Rule = "B2/S".parse().unwrap();
+        assert_eq!(solve(&state, 2, 1, &seeds, Layout::Dense), 5);
+    }
+
+    #[test]
+    fn glider_keeps_bounds_small() {
+        // the example seed is a glider in plain 2D Game of Life
+        let state = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let life = Rule::default();
+        let mut dense = Dense::new(&state, 2);
+        let mut sparse = Sparse::new(&state, 2);
+        for _ in 0..40 {
+            dense = dense.simulate_round(&life);
+            sparse = sparse.simulate_round(&life);
+        }
+        assert_eq!(dense.active(), 5);
+        assert_eq!(dense.bounds(), Some((vec![10, 10], vec![12, 12])));
+        assert_eq!(sparse.bounds(), dense.bounds());
+    }
+
+    #[test]
+    fn sparse_outgrows_packing() {
+        // 10 dimensions leave 12 bits to every coordinate, packing up to 1023
+        let row = format!("{}#", ".".repeat(1023));
+        let seed = parse_input(&row, Mode::Strict).unwrap();
+        let grow: Rule = "B1/S".parse().unwrap();
+        let next = Sparse::new(&seed, 10).simulate_round(&grow);
+        assert_eq!(next.active(), 3usize.pow(10) - 1);
+        let mut edge = vec![0; 10];
+        edge[0] = 1024;
+        assert!(next.is_active(&edge));
+        assert_eq!(next.bounds().unwrap().1[0], 1024);
+    }
+
+    #[test]
+    fn symmetric_mirrors_cells() {
+        let state = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let life = Rule::default();
+        let mut dense = Dense::new(&state, 4);
+        let mut symmetric = Symmetric::new(&state, 4);
+        for _ in 0..3 {
+            dense = dense.simulate_round(&life);
+            symmetric = symmetric.simulate_round(&life);
+        }
+        let (mut expected, mut cells) = (dense.active_cells(), symmetric.active_cells());
+        expected.sort();
+        cells.sort();
+        assert_eq!(cells, expected);
+        assert_eq!(
+            symmetric.is_active(&[2, 3, -1, 2]),
+            dense.is_active(&[2, 3, -1, 2])
+        );
+        assert!(symmetric.stored() * 2 < symmetric.active());
+    }
+
+    #[test]
+    fn renders_like_the_puzzle() {
+        let state = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let life = Rule::default();
+        let space = Dense::new(&state, 3);
+        assert_eq!(render(&space, &View::default()), "z=0\n.#.\n..#\n###\n");
+        let next = space.simulate_round(&life);
+        assert_eq!(
+            render(&next, &"all".parse().unwrap()),
+            "z=-1\n#..\n..#\n.#.\n\nz=0\n#.#\n.##\n.#.\n\nz=1\n#..\n..#\n.#.\n"
+        );
+        assert_eq!(render(&next, &"1".parse().unwrap()), "z=1\n#..\n..#\n.#.\n");
+        assert_eq!(render(&next, &"max".parse().unwrap()), "#.#\n.##\n.#.\n");
+
+        let space = Sparse::new(&state, 4).simulate_round(&life);
+        let view: View = "*,0".parse().unwrap();
+        assert_eq!(view.axes, vec![Axis::All, Axis::At(0)]);
+        assert!(render(&space, &view).starts_with("z=-1, w=0\n#..\n..#\n.#.\n\nz=0, w=0\n"));
+        assert!("0,top".parse::<View>().is_err());
+        assert_eq!(
+            render(
+                &Dense::new(&parse_input("...", Mode::Strict).unwrap(), 3),
+                &view
+            ),
+            ""
+        );
+    }
+
+    #[test]
+    fn round_matches_neighbor_count() {
+        let state = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let life = Rule::default();
+        let space = Dense::new(&state, 5).simulate_round(&life);
+        let next = space.simulate_round(&life);
+        let (min, max) = next.bounds().unwrap();
+        let mut checked = 0;
+        for offset in offsets(5) {
+            for corner in [&min, &max].iter() {
+                let pos: Vec<_> = corner.iter().zip(&offset).map(|(c, d)| c + d).collect();
+                let expected = life.next(space.is_active(&pos), space.count_neighbors(&pos));
+                assert_eq!(next.is_active(&pos), expected, "{:?}", pos);
+                checked += 1;
+            }
+        }
+        assert_eq!(checked, 2 * 243);
+        assert_eq!(offsets(3).len(), 27);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(
+            Day17::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(),
+            "112"
+        );
+        assert_eq!(
+            Day17::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(),
+            "848"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use aoc_common::{print_json, Cli, Format, Grid, Opt, Result};
+use day17::{render, simulate, solve, Day17, Layout, Rule, Space, View, DIMS, ROUNDS};
+use std::{fs, path::PathBuf};
+
+const USAGE: &str = "\
+usage: day17 [<input>|-] [--dims <n>[,<n>...]] [--layout <dense|sparse|symmetric>]
+             [--rule <B.../S...>] [--rounds <n>] [--show <view>] [--dump <dir>]
+             [--format <human|json>] [--strict]
+
+--rule takes a life-like rulestring, B3/S23 by default, e.g. B36/S23 for
+HighLife or B2/S for Seeds; --rounds defaults to 6.
+
+--show prints the space after every cycle, <view> has one comma separated
+entry per dimension past x and y: * for all slices, a coordinate for one
+slice or max for a projection, e.g. 0,max; `all` shows every slice.
+--dump writes the same drawing of every cycle to <dir>/<dims>d-cycle<n>.txt.";
+
+const OPTIONS: &[Opt] = &[
+    Opt::value("--dims"),
+    Opt::value("--layout").any_format(),
+    Opt::value("--rule"),
+    Opt::value("--rounds"),
+    Opt::value("--show"),
+    Opt::value("--dump"),
+];
+
+struct Options {
+    dims: Vec<usize>,
+    layout: Layout,
+    rule: Rule,
+    rounds: usize,
+    show: Option<View>,
+    dump: Option<PathBuf>,
+}
+
+impl Options {
+    fn from_cli(cli: &Cli) -> Result<Options> {
+        let options = Options {
+            dims: match cli.value("--dims") {
+                Some(dims) => dims.split(',').map(str::parse).collect::<Result<_, _>>()?,
+                None => DIMS.to_vec(),
+            },
+            layout: cli
+                .value("--layout")
+                .map_or(Ok(Layout::Dense), str::parse)?,
+            rule: cli
+                .value("--rule")
+                .map_or(Ok(Rule::default()), str::parse)?,
+            rounds: cli.value("--rounds").map_or(Ok(ROUNDS), str::parse)?,
+            show: cli.value("--show").map(str::parse).transpose()?,
+            dump: cli.value("--dump").map(PathBuf::from),
+        };
+        if let Some(dims) = options.dims.iter().find(|&&dims| dims < 2) {
+            return Err(format!("At least 2 dimensions are required, got {}", dims).into());
+        }
+        if let Some(view) = &options.show {
+            let min = options.dims.iter().min().unwrap_or(&2);
+            if view.axes.len() > min - 2 {
+                let msg = format!("View {:?} has more axes than {} dimensions", view.axes, min);
+                return Err(msg.into());
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn cycle_title(round: usize) -> String {
+    match round {
+        0 => "Before any cycles:".to_string(),
+        1 => "After 1 cycle:".to_string(),
+        _ => format!("After {} cycles:", round),
+    }
+}
+
+/// Simulates while printing and dumping every cycle as asked by `options`.
+fn simulate_shown(initial_state: &Grid<bool>, dims: usize, options: &Options) -> Result<usize> {
+    if let Some(dir) = &options.dump {
+        fs::create_dir_all(dir)?;
+    }
+    let view = options.show.clone().unwrap_or_default();
+    let mut observe = |round, space: &dyn Space| -> Result<()> {
+        let drawing = render(space, &view);
+        if options.show.is_some() {
+            println!("{}\n\n{}", cycle_title(round), drawing);
+        }
+        if let Some(dir) = &options.dump {
+            fs::write(dir.join(format!("{}d-cycle{}.txt", dims, round)), &drawing)?;
+        }
+        Ok(())
+    };
+    simulate(
+        initial_state,
+        dims,
+        options.rounds,
+        &options.rule,
+        options.layout,
+        &mut observe,
+    )
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = Cli::from_env_with(USAGE, OPTIONS)?;
+    let input = cli.input(17)?;
+    if cli.format == Format::Json {
+        print_json::<Day17>(17, &input, cli.mode);
+        return Ok(());
+    }
+    let options = Options::from_cli(&cli)?;
+    let initial_state = input.parse::<Day17>(cli.mode)?;
+
+    for &dims in &options.dims {
+        let active = if options.show.is_some() || options.dump.is_some() {
+            simulate_shown(&initial_state, dims, &options)?
+        } else {
+            solve(
+                &initial_state,
+                dims,
+                options.rounds,
+                &options.rule,
+                options.layout,
+            )
+        };
+        println!("{}", active);
+    }
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use crate::Space;
+use aoc_common::{Error, Grid, Result};
+use std::str::FromStr;
+
+/// What a [`View`] shows of one dimension past `x` and `y`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Axis {
+    /// Every slice along the axis, one after the other.
+    All,
+    /// Only the slice at this coordinate.
+    At(i64),
+    /// A single slice where a cube is shown if any cube along the axis is active.
+    Max,
+}
+
+/// How a space is drawn, with one [`Axis`] per extra dimension. Dimensions
+/// without one show all their slices, like the puzzle does.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct View {
+    pub axes: Vec<Axis>,
+}
+
+impl View {
+    fn axis(&self, dim: usize) -> Axis {
+        self.axes.get(dim - 2).copied().unwrap_or(Axis::All)
+    }
+}
+
+impl FromStr for View {
+    type Err = Error;
+
+    /// Parses comma separated axes, `*` for all slices, `max` for a projection
+    /// or a coordinate, e.g. `0,max`. `all` shows every slice.
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "all" {
+            return Ok(View::default());
+        }
+        let axes =
+            s.split(',')
+                .map(|axis| match axis {
+                    "*" => Ok(Axis::All),
+                    "max" => Ok(Axis::Max),
+                    _ => axis.parse().map(Axis::At).map_err(|_| {
+                        format!("Invalid axis: {}, expected *, max or a number", axis)
+                    }),
+                })
+                .collect::<Result<_, _>>()?;
+        Ok(View { axes })
+    }
+}
+
+fn axis_name(dim: usize) -> String {
+    match dim {
+        0 => "x".to_string(),
+        1 => "y".to_string(),
+        2 => "z".to_string(),
+        3 => "w".to_string(),
+        _ => format!("d{}", dim),
+    }
+}
+
+/// Every combination of the coordinates in `ranges`.
+fn combinations(ranges: &[(i64, i64)]) -> Vec<Vec<i64>> {
+    ranges
+        .iter()
+        .fold(vec![Vec::new()], |combinations, &(min, max)| {
+            combinations
+                .iter()
+                .flat_map(|combination| {
+                    (min..=max).map(move |p| {
+                        let mut combination = combination.clone();
+                        combination.push(p);
+                        combination
+                    })
+                })
+                .collect()
+        })
+}
+
+/// Draws the active cubes in the puzzle's layout, every slice headed by its
+/// extra coordinates like `z=0, w=1`, all framed by the bounds of the space.
+///
+/// Projected dimensions are left out of the headers, an empty space draws nothing.
+pub fn render(space: &dyn Space, view: &View) -> String {
+    let (min, max) = match space.bounds() {
+        Some(bounds) => bounds,
+        None => return String::new(),
+    };
+    let extra = 2..space.dims();
+    let shown: Vec<_> = extra
+        .clone()
+        .filter(|&dim| view.axis(dim) != Axis::Max)
+        .collect();
+    let projected: Vec<_> = extra.filter(|&dim| view.axis(dim) == Axis::Max).collect();
+    let ranges = |dims: &[usize]| -> Vec<(i64, i64)> {
+        dims.iter()
+            .map(|&dim| match view.axis(dim) {
+                Axis::At(p) => (p, p),
+                _ => (min[dim], max[dim]),
+            })
+            .collect()
+    };
+    let depths = combinations(&ranges(&projected));
+
+    let (width, height) = (
+        (max[0] - min[0] + 1) as usize,
+        (max[1] - min[1] + 1) as usize,
+    );
+    let mut slices = Vec::new();
+    for slice in combinations(&ranges(&shown)) {
+        let mut pos = vec![0; space.dims()];
+        for (&dim, &p) in shown.iter().zip(&slice) {
+            pos[dim] = p;
+        }
+        let mut grid = Grid::filled(width, height, false);
+        for (x, y) in grid.positions() {
+            pos[0] = min[0] + x as i64;
+            pos[1] = min[1] + y as i64;
+            grid[(x, y)] = depths.iter().any(|depth| {
+                for (&dim, &p) in projected.iter().zip(depth) {
+                    pos[dim] = p;
+                }
+                space.is_active(&pos)
+            });
+        }
+        let mut out: String = shown
+            .iter()
+            .zip(&slice)
+            .map(|(&dim, p)| format!("{}={}", axis_name(dim), p))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out += &grid.render(|&active| if active { '#' } else { '.' });
+        slices.push(out);
+    }
+    slices.join("\n")
+}
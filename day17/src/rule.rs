@@ -0,0 +1,84 @@
+use aoc_common::{Error, Result};
+use std::{collections::BTreeSet, fmt, str::FromStr};
+
+/// Life-like rule telling which neighbor counts give birth to an inactive cube
+/// and which keep an active one alive, written as a `B.../S...` rulestring.
+///
+/// Every digit is a count, counts above 9 are written as comma separated
+/// numbers instead, e.g. `B3,10/S2,3`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rule {
+    birth: BTreeSet<usize>,
+    survive: BTreeSet<usize>,
+}
+
+/// Rule of the puzzle, and of Conway's Game of Life.
+pub const CONWAY: &str = "B3/S23";
+
+impl Rule {
+    /// Whether a cube with `neighbors` active neighbors is active next round.
+    pub fn next(&self, active: bool, neighbors: usize) -> bool {
+        let counts = if active { &self.survive } else { &self.birth };
+        counts.contains(&neighbors)
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        CONWAY.parse().unwrap()
+    }
+}
+
+fn parse_counts(s: &str) -> Result<BTreeSet<usize>> {
+    let counts = if s.contains(',') {
+        s.split(',').map(str::parse).collect::<Result<_, _>>()?
+    } else {
+        s.chars()
+            .map(|c| c.to_digit(10).map(|d| d as usize))
+            .collect::<Option<_>>()
+            .ok_or_else(|| format!("Invalid neighbor counts: {}", s))?
+    };
+    Ok(counts)
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || format!("Invalid rule: {}, expected B<counts>/S<counts>", s);
+        let (birth, survive) = s.split_once('/').ok_or_else(invalid)?;
+        let birth = birth
+            .strip_prefix(|c| c == 'B' || c == 'b')
+            .ok_or_else(invalid)?;
+        let survive = survive
+            .strip_prefix(|c| c == 'S' || c == 's')
+            .ok_or_else(invalid)?;
+        let rule = Rule {
+            birth: parse_counts(birth)?,
+            survive: parse_counts(survive)?,
+        };
+        if rule.next(false, 0) {
+            return Err(format!("Rule {} would activate the whole infinite space", s).into());
+        }
+        Ok(rule)
+    }
+}
+
+fn write_counts(f: &mut fmt::Formatter<'_>, counts: &BTreeSet<usize>) -> fmt::Result {
+    let separator = if counts.iter().any(|&count| count > 9) {
+        ","
+    } else {
+        ""
+    };
+    let counts: Vec<_> = counts.iter().map(usize::to_string).collect();
+    write!(f, "{}", counts.join(separator))
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "B")?;
+        write_counts(f, &self.birth)?;
+        write!(f, "/S")?;
+        write_counts(f, &self.survive)
+    }
+}
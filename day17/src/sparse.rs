@@ -0,0 +1,204 @@
+use crate::{offsets, rule::Rule, Space};
+use aoc_common::Grid;
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasherDefault, Hasher},
+};
+
+/// Hasher for packed positions, SipHash is several times slower and its
+/// DoS resistance isn't needed here.
+#[derive(Default)]
+struct PackedHasher(u64);
+
+impl Hasher for PackedHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0.rotate_left(8) ^ b as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+        }
+    }
+
+    fn write_i128(&mut self, n: i128) {
+        self.0 = (n as u64) ^ ((n >> 64) as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    }
+
+    fn finish(&self) -> u64 {
+        // murmur3 finalizer, spreads every input bit over the whole hash
+        let mut h = self.0;
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+        h ^= h >> 33;
+        h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+        h ^ (h >> 33)
+    }
+}
+
+type Packed = BuildHasherDefault<PackedHasher>;
+
+/// Positions of the active cubes of a [`Sparse`] space.
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Cells {
+    /// Packed into a single integer, every coordinate gets an equal share of
+    /// its bits.
+    Packed(HashSet<i128, Packed>),
+    /// As they are, once some coordinate outgrows its share.
+    Wide(HashSet<Vec<i64>>),
+}
+
+/// Cubes of a `dims`-dimensional space stored as the set of active ones,
+/// only their surroundings are visited every round.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Sparse {
+    dims: usize,
+    cells: Cells,
+}
+
+impl Sparse {
+    fn bits(&self) -> usize {
+        128 / self.dims
+    }
+
+    fn bias(&self) -> i64 {
+        1 << (self.bits() - 2)
+    }
+
+    /// Whether every coordinate of `pos` fits its share of a packed position.
+    fn fits(&self, pos: &[i64]) -> bool {
+        self.bits() >= 2 && pos.iter().all(|p| p.unsigned_abs() < self.bias() as u64)
+    }
+
+    /// Whether the cubes of the next round can all be packed.
+    fn next_fits(&self) -> bool {
+        let (min, max) = match self.bounds() {
+            Some(bounds) => bounds,
+            None => return true,
+        };
+        let grown = |pos: Vec<i64>, by| pos.iter().map(|p| p + by).collect::<Vec<_>>();
+        self.fits(&grown(min, -1)) && self.fits(&grown(max, 1))
+    }
+
+    fn pack(&self, pos: &[i64]) -> i128 {
+        let (bits, bias) = (self.bits(), self.bias());
+        pos.iter()
+            .rev()
+            .fold(0, |key, &p| key << bits | (p + bias) as i128)
+    }
+
+    fn unpack(&self, mut key: i128) -> Vec<i64> {
+        let (bits, bias) = (self.bits(), self.bias());
+        (0..self.dims)
+            .map(|_| {
+                let p = (key & ((1 << bits) - 1)) as i64 - bias;
+                key >>= bits;
+                p
+            })
+            .collect()
+    }
+
+    fn round_packed(&self, active: &HashSet<i128, Packed>, rule: &Rule) -> HashSet<i128, Packed> {
+        // the packing is linear, so moving by an offset is adding its packed form
+        let zero = self.pack(&vec![0; self.dims]);
+        // active cubes count themselves too so that lonely ones are visited as well
+        let deltas: Vec<i128> = offsets(self.dims)
+            .iter()
+            .map(|offset| self.pack(offset) - zero)
+            .collect();
+        let mut neighbors: HashMap<i128, u32, Packed> =
+            HashMap::with_capacity_and_hasher(active.len() * 4, Packed::default());
+        for &key in active {
+            for &delta in &deltas {
+                *neighbors.entry(key + delta).or_insert(0) += 1;
+            }
+        }
+        neighbors
+            .into_iter()
+            .filter(|(key, count)| {
+                let active = active.contains(key);
+                rule.next(active, (count - active as u32) as usize)
+            })
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    fn round_wide(&self, rule: &Rule) -> HashSet<Vec<i64>> {
+        let offsets = offsets(self.dims);
+        let mut neighbors: HashMap<Vec<i64>, u32> = HashMap::new();
+        for pos in self.active_cells() {
+            for offset in &offsets {
+                let neighbor = pos.iter().zip(offset).map(|(p, d)| p + d).collect();
+                *neighbors.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+        neighbors
+            .into_iter()
+            .filter(|(pos, count)| {
+                let active = self.is_active(pos);
+                rule.next(active, (count - active as u32) as usize)
+            })
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+}
+
+impl Space for Sparse {
+    fn new(seed: &Grid<bool>, dims: usize) -> Self {
+        assert!(dims >= 2, "the seed needs at least two dimensions");
+        let cells: Vec<Vec<i64>> = seed
+            .positions()
+            .filter(|&pos| seed[pos])
+            .map(|(x, y)| {
+                let mut pos = vec![0; dims];
+                pos[0] = x as i64;
+                pos[1] = y as i64;
+                pos
+            })
+            .collect();
+        let mut space = Sparse {
+            dims,
+            cells: Cells::Wide(HashSet::new()),
+        };
+        space.cells = if cells.iter().all(|pos| space.fits(pos)) {
+            Cells::Packed(cells.iter().map(|pos| space.pack(pos)).collect())
+        } else {
+            Cells::Wide(cells.into_iter().collect())
+        };
+        space
+    }
+
+    fn dims(&self) -> usize {
+        self.dims
+    }
+
+    fn is_active(&self, pos: &[i64]) -> bool {
+        match &self.cells {
+            Cells::Packed(active) => self.fits(pos) && active.contains(&self.pack(pos)),
+            Cells::Wide(active) => active.contains(pos),
+        }
+    }
+
+    fn active(&self) -> usize {
+        match &self.cells {
+            Cells::Packed(active) => active.len(),
+            Cells::Wide(active) => active.len(),
+        }
+    }
+
+    fn active_cells(&self) -> Vec<Vec<i64>> {
+        match &self.cells {
+            Cells::Packed(active) => active.iter().map(|&key| self.unpack(key)).collect(),
+            Cells::Wide(active) => active.iter().cloned().collect(),
+        }
+    }
+
+    fn simulate_round(&self, rule: &Rule) -> Sparse {
+        let cells = match &self.cells {
+            Cells::Packed(active) if self.next_fits() => {
+                Cells::Packed(self.round_packed(active, rule))
+            }
+            _ => Cells::Wide(self.round_wide(rule)),
+        };
+        Sparse {
+            dims: self.dims,
+            cells,
+        }
+    }
+}
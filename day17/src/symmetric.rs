@@ -0,0 +1,134 @@
+use crate::{dense::Dense, offsets, rule::Rule, Space};
+use aoc_common::Grid;
+
+/// Cubes of a `dims`-dimensional space seeded in the `x`/`y` plane.
+///
+/// Such a space stays mirror symmetric around zero in every extra dimension,
+/// so only the half with non-negative extra coordinates is stored, as a
+/// [`Dense`] box, and the other half is folded onto it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Symmetric {
+    half: Dense,
+}
+
+/// Mirrors `pos` into the stored half.
+fn fold(pos: &[i64]) -> Vec<i64> {
+    pos.iter()
+        .enumerate()
+        .map(|(d, &p)| if d < 2 { p } else { p.abs() })
+        .collect()
+}
+
+/// Extra dimensions in which `pos` is at `value`, as a bit mask.
+fn extra_mask(pos: &[i64], value: i64) -> u64 {
+    pos[2..]
+        .iter()
+        .enumerate()
+        .filter(|&(_, &p)| p == value)
+        .fold(0, |mask, (d, _)| mask | 1 << d)
+}
+
+/// Every mirror image of a stored cube, each nonzero extra coordinate doubles them.
+fn images(pos: &[i64]) -> Vec<Vec<i64>> {
+    (2..pos.len()).fold(vec![pos.to_vec()], |images, d| {
+        images
+            .into_iter()
+            .flat_map(|image| {
+                let mut mirrored = image.clone();
+                mirrored[d] = -mirrored[d];
+                let mirrored = Some(mirrored).filter(|_| image[d] != 0);
+                Some(image).into_iter().chain(mirrored)
+            })
+            .collect()
+    })
+}
+
+impl Symmetric {
+    /// Number of cubes actually stored, mirror images not counted.
+    pub fn stored(&self) -> usize {
+        self.half.active()
+    }
+}
+
+impl Space for Symmetric {
+    fn new(seed: &Grid<bool>, dims: usize) -> Self {
+        Symmetric {
+            half: Dense::new(seed, dims),
+        }
+    }
+
+    fn dims(&self) -> usize {
+        self.half.dims()
+    }
+
+    fn is_active(&self, pos: &[i64]) -> bool {
+        self.half.is_active(&fold(pos))
+    }
+
+    fn active(&self) -> usize {
+        let extra = self.dims() - 2;
+        self.half
+            .active_positions()
+            .map(|pos| 1 << (extra - extra_mask(&pos, 0).count_ones() as usize))
+            .sum()
+    }
+
+    fn active_cells(&self) -> Vec<Vec<i64>> {
+        self.half
+            .active_positions()
+            .flat_map(|pos| images(&pos))
+            .collect()
+    }
+
+    fn simulate_round(&self, rule: &Rule) -> Symmetric {
+        let (min, max) = match self.half.bounds() {
+            Some(bounds) => bounds,
+            None => return self.clone(),
+        };
+        // like Dense, but the box never reaches below zero in extra dimensions
+        let origin: Vec<_> = min
+            .iter()
+            .enumerate()
+            .map(|(d, &min)| if d < 2 { min - 1 } else { (min - 1).max(0) })
+            .collect();
+        let size = origin
+            .iter()
+            .zip(max.iter())
+            .map(|(origin, max)| (max - origin) as usize + 2)
+            .collect();
+        let mut next = Dense::empty(origin, size);
+
+        // a step to -1 from 0 leaves the half, from 1 it also lands on the
+        // mirror image of the cube reached from -1, which counts it twice
+        let deltas: Vec<(isize, u64)> = offsets(self.dims())
+            .iter()
+            .map(|offset| {
+                let delta = offset
+                    .iter()
+                    .zip(next.strides.iter())
+                    .map(|(&d, &stride)| d as isize * stride as isize)
+                    .sum();
+                (delta, extra_mask(offset, -1))
+            })
+            .collect();
+        let mut was_active = vec![false; next.cells.len()];
+        // active cubes count themselves too, which is subtracted below
+        let mut neighbors = vec![0u32; next.cells.len()];
+        for pos in self.half.active_positions() {
+            let idx = next.index(&pos).unwrap();
+            was_active[idx] = true;
+            let (zeros, ones) = (extra_mask(&pos, 0), extra_mask(&pos, 1));
+            for &(delta, down) in &deltas {
+                if down & zeros == 0 {
+                    neighbors[(idx as isize + delta) as usize] += 1 << (down & ones).count_ones();
+                }
+            }
+        }
+
+        for (idx, cell) in next.cells.iter_mut().enumerate() {
+            let active = was_active[idx];
+            *cell = rule.next(active, (neighbors[idx] - active as u32) as usize);
+        }
+        Symmetric { half: next }
+    }
+}
@@ -0,0 +1,290 @@
+use aoc_common::{collect, Diagnostic, Error, Mode, Result, Solution, Source};
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Term(i64),
+    Adition(Box<Expr>, Box<Expr>),
+    Mult(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Lex {
+    LB,
+    RB,
+    Num(i64),
+    Plus,
+    Mult,
+    Eof,
+}
+
+#[derive(Debug)]
+pub enum ParsingError {
+    InvalidPrimaryExpression(Lex),
+    ExpectedBracket(Lex),
+}
+
+impl std::fmt::Display for ParsingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParsingError::InvalidPrimaryExpression(l) => {
+                write!(f, "Invalid primary expression: {:?}", l)
+            }
+            ParsingError::ExpectedBracket(l) => write!(f, "Expected bracket, found: {:?}", l),
+        }
+    }
+}
+
+impl std::error::Error for ParsingError {}
+
+pub type ParsingResult<'a> = Result<(Expr, &'a [Lex]), ParsingError>;
+
+/// Lexes a single expression.
+pub fn lex(input: &str) -> Result<Vec<Lex>, Diagnostic> {
+    lex_line(&Source::new(input), input)
+}
+
+/// Lexes `line` of the input, pointing diagnostics at it.
+pub fn lex_line<'a>(source: &Source<'a>, line: &'a str) -> Result<Vec<Lex>, Diagnostic> {
+    line.char_indices()
+        .filter(|(_, c)| !c.is_whitespace())
+        .map(|(idx, c)| match c {
+            '(' => Ok(Lex::LB),
+            ')' => Ok(Lex::RB),
+            '+' => Ok(Lex::Plus),
+            '*' => Ok(Lex::Mult),
+            d if d.is_ascii_digit() => Ok(Lex::Num(d.to_digit(10).unwrap() as i64)),
+            _ => Err(source.error(&line[idx..], "digit, operator or bracket")),
+        })
+        .chain(Some(Ok(Lex::Eof)))
+        .collect()
+}
+
+/// The next token, `Eof` past the end.
+fn peek(rest: &[Lex]) -> Lex {
+    rest.first().copied().unwrap_or(Lex::Eof)
+}
+
+pub fn parse<'a>(rest: &'a [Lex], op_precedence: &'static dyn Fn(&Lex) -> i8) -> ParsingResult<'a> {
+    let (lhs, rest) = parse_primary(rest, op_precedence)?;
+    parse_binary_ops_rhs(rest, 0, lhs, op_precedence)
+}
+
+pub fn parse_primary<'a>(
+    rest: &'a [Lex],
+    op_precedence: &'static dyn Fn(&Lex) -> i8,
+) -> ParsingResult<'a> {
+    match peek(rest) {
+        Lex::Num(n) => Ok((Expr::Term(n), &rest[1..])),
+        Lex::LB => parse_paren(&rest[1..], op_precedence),
+        l => Err(ParsingError::InvalidPrimaryExpression(l)),
+    }
+}
+
+pub fn parse_paren<'a>(
+    rest: &'a [Lex],
+    op_precedence: &'static dyn Fn(&Lex) -> i8,
+) -> ParsingResult<'a> {
+    let (expr, rest) = parse(rest, op_precedence)?;
+    match peek(rest) {
+        Lex::RB => Ok((expr, &rest[1..])),
+        l => Err(ParsingError::ExpectedBracket(l)),
+    }
+}
+
+pub fn parse_binary_ops_rhs<'a>(
+    mut rest: &'a [Lex],
+    expr_precedence: i8,
+    mut lhs: Expr,
+    op_precedence: &'static dyn Fn(&Lex) -> i8,
+) -> ParsingResult<'a> {
+    loop {
+        let token = peek(rest);
+        let tok_precedence = op_precedence(&token);
+        if tok_precedence < expr_precedence {
+            return Ok((lhs, rest));
+        } else {
+            let (mut right_expr, new_rest) = parse_primary(&rest[1..], op_precedence)?;
+            rest = new_rest;
+            if tok_precedence < op_precedence(&peek(rest)) {
+                let (new_rhs, new_rest) =
+                    parse_binary_ops_rhs(rest, expr_precedence + 1, right_expr, op_precedence)?;
+                rest = new_rest;
+                right_expr = new_rhs;
+            }
+            lhs = binary_op_expr(&token, lhs, right_expr)
+        }
+    }
+}
+
+pub fn binary_op_expr(binary_op: &Lex, lhs: Expr, rhs: Expr) -> Expr {
+    match *binary_op {
+        Lex::Plus => Expr::Adition(Box::new(lhs), Box::new(rhs)),
+        Lex::Mult => Expr::Mult(Box::new(lhs), Box::new(rhs)),
+        _ => unreachable!(),
+    }
+}
+
+pub fn op_precedence_part1(tok: &Lex) -> i8 {
+    match *tok {
+        Lex::Plus => 10,
+        Lex::Mult => 10,
+        Lex::Num(_) => -1,
+        Lex::LB => -1,
+        Lex::RB => -1,
+        Lex::Eof => -1,
+    }
+}
+
+pub fn op_precedence_part2(tok: &Lex) -> i8 {
+    match *tok {
+        Lex::Plus => 20,
+        Lex::Mult => 10,
+        Lex::Num(_) => -1,
+        Lex::LB => -1,
+        Lex::RB => -1,
+        Lex::Eof => -1,
+    }
+}
+
+pub fn eval(expr: &Expr) -> i64 {
+    match expr {
+        Expr::Term(n) => *n,
+        Expr::Adition(lhs, rhs) => eval(lhs) + eval(rhs),
+        Expr::Mult(lhs, rhs) => eval(lhs) * eval(rhs),
+    }
+}
+
+#[allow(unused)]
+pub fn print(expr: &Expr) {
+    match expr {
+        Expr::Term(n) => print!("{}", n),
+        Expr::Adition(lhs, rhs) => {
+            print!("(");
+            print(lhs);
+            print!(" + ");
+            print(rhs);
+            print!(")");
+        }
+        Expr::Mult(lhs, rhs) => {
+            print!("(");
+            print(lhs);
+            print!(" * ");
+            print(rhs);
+            print!(")");
+        }
+    }
+}
+
+impl From<ParsingError> for Error {
+    fn from(e: ParsingError) -> Self {
+        Error::Parse(e.to_string())
+    }
+}
+
+/// Evaluates every lexed line and sums the results.
+pub fn sum(
+    lines: &[Vec<Lex>],
+    op_precedence: &'static dyn Fn(&Lex) -> i8,
+) -> Result<i64, ParsingError> {
+    lines
+        .iter()
+        .map(|lexems| parse(lexems, op_precedence).map(|expr| eval(&expr.0)))
+        .sum()
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Vec<Lex>>;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        let source = Source::new(input);
+        Ok(collect(
+            input.lines().map(|line| lex_line(&source, line)),
+            mode,
+        )?)
+    }
+
+    fn part1(lines: &Self::Input) -> Result<String> {
+        Ok(sum(lines, &op_precedence_part1)?.to_string())
+    }
+
+    fn part2(lines: &Self::Input) -> Result<String> {
+        Ok(sum(lines, &op_precedence_part2)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval_str(input: &str, op_precedence: &'static dyn Fn(&Lex) -> i8) -> i64 {
+        let lexems = lex(input).unwrap();
+        let (expr, rest) = parse(&lexems, op_precedence).unwrap();
+        assert_eq!(rest, &[Lex::Eof]);
+        eval(&expr)
+    }
+
+    const EXAMPLES: [(&str, i64, i64); 6] = [
+        ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+        ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+        ("2 * 3 + (4 * 5)", 26, 46),
+        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+        ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+        (
+            "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+            13632,
+            23340,
+        ),
+    ];
+
+    #[test]
+    fn same_precedence() {
+        for &(input, expected, _) in EXAMPLES.iter() {
+            assert_eq!(eval_str(input, &op_precedence_part1), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn addition_first() {
+        for &(input, _, expected) in EXAMPLES.iter() {
+            assert_eq!(eval_str(input, &op_precedence_part2), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn unbalanced_brackets() {
+        assert!(parse(&lex("(1 + 2").unwrap(), &op_precedence_part1).is_err());
+        assert!(parse(&lex("* 2").unwrap(), &op_precedence_part1).is_err());
+        assert!(parse(&[], &op_precedence_part1).is_err());
+        assert!(parse(&[Lex::LB, Lex::Num(1)], &op_precedence_part1).is_err());
+    }
+
+    #[test]
+    fn unexpected_chars() {
+        let err = lex("1 + a").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 5));
+        assert_eq!(err.expected, "digit, operator or bracket");
+        let err = Day18::parse("1 + 2\n3 - 1\n", Mode::Strict).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected digit, operator or bracket, found \"- 1\""
+        );
+        assert_eq!(
+            Day18::parse("1 + 2\n3 - 1\n", Mode::Lenient).unwrap().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn sum_of_lines() {
+        let lines: Vec<_> = EXAMPLES
+            .iter()
+            .map(|(input, _, _)| lex(input).unwrap())
+            .collect();
+        let total1: i64 = EXAMPLES.iter().map(|(_, p1, _)| p1).sum();
+        let total2: i64 = EXAMPLES.iter().map(|(_, _, p2)| p2).sum();
+        assert_eq!(sum(&lines, &op_precedence_part1).unwrap(), total1);
+        assert_eq!(sum(&lines, &op_precedence_part2).unwrap(), total2);
+    }
+}
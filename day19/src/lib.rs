@@ -0,0 +1,409 @@
+use aoc_common::{sections, Diagnostic, Mode, Result, Solution, Source};
+use std::collections::{HashMap, VecDeque};
+
+#[derive(Debug, Clone)]
+pub enum Rule {
+    Term(char),
+    NonTerm(Vec<usize>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Expr {
+    rules: Vec<Rule>,
+}
+
+pub fn split_input(input: &str) -> Option<(&str, &str)> {
+    let mut split = sections(input);
+    Some((split.next()?, split.next()?))
+}
+
+fn parse_alternative<'a>(source: &Source<'a>, alternative: &'a str) -> Result<Rule, Diagnostic> {
+    if alternative.trim().is_empty() {
+        return Err(source.error(alternative, "rule number"));
+    }
+    let parts = alternative
+        .split_whitespace()
+        .map(|v| source.parse(v, "rule number"))
+        .collect::<Result<_, _>>()?;
+    Ok(Rule::NonTerm(parts))
+}
+
+fn parse_expr<'a>(source: &Source<'a>, rule: &'a str) -> Result<Expr, Diagnostic> {
+    if let Some(term) = rule.strip_prefix('"') {
+        let c = term.chars().next().filter(|&c| c != '"');
+        let c = c.ok_or_else(|| source.error(term, "character"))?;
+        return Ok(Expr {
+            rules: vec![Rule::Term(c)],
+        });
+    }
+    let rules = rule
+        .split('|')
+        .map(|alternative| parse_alternative(source, alternative))
+        .collect::<Result<_, _>>()?;
+    Ok(Expr { rules })
+}
+
+pub fn parse_rules<'a>(
+    source: &Source<'a>,
+    input: &'a str,
+) -> Result<HashMap<usize, Expr>, Diagnostic> {
+    input
+        .lines()
+        .map(|line| {
+            let (n, rule) = source.split(line, ": ")?;
+            Ok((source.parse(n, "rule number")?, parse_expr(source, rule)?))
+        })
+        .collect()
+}
+
+/// Checks that rule 0 and every rule referenced by another one are defined.
+pub fn check_rules(rules: &HashMap<usize, Expr>) -> Result<()> {
+    let referenced = rules.values().flat_map(|expr| &expr.rules);
+    let referenced = referenced.flat_map(|rule| match rule {
+        Rule::Term(_) => &[][..],
+        Rule::NonTerm(parts) => &parts[..],
+    });
+    match std::iter::once(&0)
+        .chain(referenced)
+        .find(|n| !rules.contains_key(n))
+    {
+        Some(n) => Err(format!("Rule {} is not defined", n).into()),
+        None => Ok(()),
+    }
+}
+
+pub fn bin(rules: &HashMap<usize, Expr>) -> HashMap<usize, Expr> {
+    let mut to_process: VecDeque<(usize, Expr)> =
+        rules.iter().map(|(k, v)| (*k, v.clone())).collect();
+    let mut max_rule_id = *rules.keys().max().unwrap();
+    let mut done = HashMap::new();
+
+    while let Some((n, expr)) = to_process.pop_front() {
+        //match rule {
+        //    Rule::Term(_) => {
+        //        done.insert(n, rule);
+        //    }
+        //    Rule::Rules(parts) => {
+        //        let r = parts
+        //            .iter()
+        //            .map(|part| {
+        //                if part.len() <= 2 {
+        //                    part.clone()
+        //                } else {
+        //                    max_rule_id += 1;
+        //                    let new_rule = Rule::Rules(vec![part[1..].to_vec()]);
+        //                    to_process.push_back((max_rule_id, new_rule));
+        //                    vec![part[0], max_rule_id]
+        //                }
+        //            })
+        //            .collect();
+        //        done.insert(n, Rule::Rules(r));
+        //    }
+        //}
+        let rules = expr
+            .rules
+            .iter()
+            .map(|rule| match rule {
+                Rule::Term(_) => rule.clone(),
+                Rule::NonTerm(part) => {
+                    if part.len() <= 2 {
+                        rule.clone()
+                    } else {
+                        max_rule_id += 1;
+                        let new_expr = Expr {
+                            rules: vec![Rule::NonTerm(part[1..].to_vec())],
+                        };
+                        to_process.push_back((max_rule_id, new_expr));
+                        Rule::NonTerm(vec![part[0], max_rule_id])
+                    }
+                }
+            })
+            .collect();
+        done.insert(n, Expr { rules });
+    }
+    done
+}
+
+pub fn unit(rules: &HashMap<usize, Expr>) -> HashMap<usize, Expr> {
+    let mut done = HashMap::new();
+    let mut to_process: VecDeque<(usize, Expr)> =
+        rules.iter().map(|(k, v)| (*k, v.clone())).collect();
+    while let Some((n, expr)) = to_process.pop_front() {
+        let mut new_parts: Vec<Rule> = Vec::new();
+        let mut change = false;
+        for idx in 0..expr.rules.len() {
+            match &expr.rules[idx] {
+                Rule::Term(_) => {
+                    new_parts.push(expr.rules[idx].clone());
+                }
+                Rule::NonTerm(nonterm) => {
+                    if nonterm.len() == 1 {
+                        let subst = rules.get(&nonterm[0]).unwrap().clone();
+                        new_parts.extend(subst.rules);
+                        change = true;
+                    } else {
+                        new_parts.push(expr.rules[idx].clone());
+                    }
+                }
+            }
+        }
+        if change {
+            to_process.push_back((n, Expr { rules: new_parts }));
+        } else {
+            done.insert(n, expr);
+        }
+    }
+    done
+}
+
+#[allow(unused)]
+pub fn print_rules(rules: &HashMap<usize, Expr>) {
+    let mut sorted = rules.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|(k, _)| **k);
+
+    for (n, expr) in sorted {
+        print!("{} -> ", n);
+        let tmp = expr
+            .rules
+            .iter()
+            .map(|rule| match rule {
+                Rule::Term(r) => format!("\"{}\" ", r),
+                Rule::NonTerm(r) => r.iter().map(|r| format!("{} ", r)).collect(),
+            })
+            .collect::<Vec<_>>();
+        print!("{}", tmp.join("| "));
+        println!();
+    }
+}
+
+pub fn ch_normal_form(rules: &HashMap<usize, Expr>) -> HashMap<usize, Expr> {
+    // START
+    // TERM
+    // BIN
+    let rules = bin(rules);
+    // DEL
+    // UNIT
+    unit(&rules)
+}
+
+pub fn cyk(string: &[char], rules: &HashMap<usize, Expr>) -> bool {
+    let n = string.len();
+    if n == 0 {
+        return false;
+    }
+    let symbols = rules.keys().max().map_or(0, |&max| max + 1);
+    let mut p = vec![vec![vec![false; symbols]; n]; n];
+    for (s, c) in string.iter().enumerate() {
+        for (&v, expr) in rules {
+            for rule in &expr.rules {
+                if let Rule::Term(a) = rule {
+                    if c == a {
+                        p[0][s][v] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    for l in 1..n {
+        for s in 0..n - l {
+            for prt in 1..=l {
+                for (&a, expr) in rules {
+                    for rule in &expr.rules {
+                        if let Rule::NonTerm(rule) = rule {
+                            let b = rule[0];
+                            let c = rule[1];
+                            if p[prt - 1][s][b] && p[l - prt][s + prt][c] {
+                                p[l][s][a] = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    p[n - 1][0][0]
+}
+
+/// Replacement rules which turn rules 8 and 11 into loops in the second part.
+pub const LOOP_RULES: &str = "8: 42 | 42 8\n11: 42 31 | 42 11 31";
+
+pub fn count_matching(rules: &HashMap<usize, Expr>, strings: &[String]) -> Result<usize> {
+    check_rules(rules)?;
+    let rules = ch_normal_form(rules);
+    Ok(strings
+        .iter()
+        .filter(|s| cyk(&s.chars().collect::<Vec<_>>(), &rules))
+        .count())
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = (HashMap<usize, Expr>, Vec<String>);
+
+    fn parse(input: &str, _mode: Mode) -> Result<Self::Input> {
+        let (rules, strings) = split_input(input).ok_or("Missing input section")?;
+        Ok((
+            parse_rules(&Source::new(input), rules)?,
+            strings.lines().map(str::to_owned).collect(),
+        ))
+    }
+
+    fn part1((rules, strings): &Self::Input) -> Result<String> {
+        Ok(count_matching(rules, strings)?.to_string())
+    }
+
+    fn part2((rules, strings): &Self::Input) -> Result<String> {
+        if !rules.contains_key(&42) || !rules.contains_key(&31) {
+            return Err("Rules 42 and 31 are required for the second part".into());
+        }
+        let mut rules = rules.clone();
+        rules.extend(parse_rules(&Source::new(LOOP_RULES), LOOP_RULES)?);
+        Ok(count_matching(&rules, strings)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "\
+0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb
+";
+
+    const LOOPS: &str = "\
+42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: \"a\"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: \"b\"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba
+";
+
+    fn matches(input: &str, s: &str) -> bool {
+        let (rules, _) = Day19::parse(input, Mode::Strict).unwrap();
+        cyk(&s.chars().collect::<Vec<_>>(), &ch_normal_form(&rules))
+    }
+
+    #[test]
+    fn single_messages() {
+        assert!(matches(EXAMPLE, "ababbb"));
+        assert!(matches(EXAMPLE, "abbbab"));
+        assert!(!matches(EXAMPLE, "bababa"));
+        assert!(!matches(EXAMPLE, "aaabbb"));
+        assert!(!matches(EXAMPLE, "aaaabbb"));
+    }
+
+    #[test]
+    fn normal_form_is_binary() {
+        let (rules, _) = Day19::parse(LOOPS, Mode::Strict).unwrap();
+        for expr in ch_normal_form(&rules).values() {
+            for rule in &expr.rules {
+                if let Rule::NonTerm(parts) = rule {
+                    assert_eq!(parts.len(), 2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(Day19::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(), "2");
+        assert_eq!(Day19::solve(LOOPS, Part::One, Mode::Strict).unwrap(), "3");
+        assert_eq!(Day19::solve(LOOPS, Part::Two, Mode::Strict).unwrap(), "12");
+        assert!(Day19::solve(EXAMPLE, Part::Two, Mode::Strict).is_err());
+    }
+
+    #[test]
+    fn malformed_rules() {
+        let err = |input: &str| match Day19::parse(input, Mode::Strict) {
+            Err(aoc_common::Error::Diagnostic(d)) => (d.line, d.column, d.expected),
+            _ => panic!("expected a diagnostic"),
+        };
+        let number = "rule number".to_owned();
+        assert_eq!(
+            err(&EXAMPLE.replace("1: 2 3", "1: 2 x")),
+            (2, 6, number.clone())
+        );
+        assert_eq!(
+            err(&EXAMPLE.replace("1: 2 3 |", "1: 2 3 ||")),
+            (2, 9, number)
+        );
+        assert_eq!(
+            err(&EXAMPLE.replace("4: \"a\"", "4: \"\"")),
+            (5, 5, "character".to_owned())
+        );
+        assert_eq!(
+            err(&EXAMPLE.replace("5: ", "5 ")),
+            (6, 6, "\": \"".to_owned())
+        );
+    }
+
+    #[test]
+    fn undefined_rules() {
+        let input = EXAMPLE.replace("0: 4 1 5", "0: 4 1 6");
+        let err = Day19::solve(&input, Part::One, Mode::Strict).unwrap_err();
+        assert_eq!(err.to_string(), "Rule 6 is not defined");
+        let input = EXAMPLE.replace("0: 4 1 5", "7: 4 1 5");
+        assert!(Day19::solve(&input, Part::One, Mode::Strict).is_err());
+        assert!(!cyk(
+            &[],
+            &ch_normal_form(&Day19::parse(EXAMPLE, Mode::Strict).unwrap().0)
+        ));
+    }
+}
@@ -0,0 +1,364 @@
+use aoc_common::{collect, Diagnostic, Mode, Result, Solution, Source};
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub mod policy;
+
+pub use policy::{parse_policy, Indexing, PasswordPolicy};
+
+/// A password with the letter and the two numbers of its policy.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PasswordEntry {
+    /// Line of the entry, counted from 1.
+    pub line: usize,
+    /// At least 1, so it can be a position too.
+    pub low: usize,
+    /// At least `low`.
+    pub high: usize,
+    /// A single grapheme cluster.
+    pub letter: String,
+    pub password: String,
+}
+
+impl fmt::Display for PasswordEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}-{} {}: {}",
+            self.low, self.high, self.letter, self.password
+        )
+    }
+}
+
+fn parse_entry<'a>(
+    source: &Source<'a>,
+    line: usize,
+    s: &'a str,
+) -> Result<PasswordEntry, Diagnostic> {
+    let (low, rest) = source.split(s, "-")?;
+    let (high, rest) = source.split(rest, " ")?;
+    let (letter, password) = source.split(rest, ":")?;
+    let (low_text, high_text) = (low, high);
+    let low = source.parse(low_text, "number")?;
+    if low == 0 {
+        return Err(source.error(low_text, "position from 1"));
+    }
+    let high = source.parse(high_text, "number")?;
+    if high < low {
+        return Err(source.error(high_text, format!("number at least {}", low)));
+    }
+    let mut clusters = letter.graphemes(true);
+    let first = clusters
+        .next()
+        .ok_or_else(|| source.error(letter, "letter"))?;
+    if clusters.next().is_some() {
+        return Err(source.error(&letter[first.len()..], "\":\""));
+    }
+    Ok(PasswordEntry {
+        line,
+        low,
+        high,
+        letter: first.to_owned(),
+        password: password.trim().to_owned(),
+    })
+}
+
+pub fn parse_input(input: &str, mode: Mode) -> Result<Vec<PasswordEntry>, Diagnostic> {
+    let source = Source::new(input);
+    let entries = input.lines().enumerate();
+    collect(entries.map(|(i, l)| parse_entry(&source, i + 1, l)), mode)
+}
+
+/// How an entry fares against a set of policies.
+pub struct Report<'a> {
+    pub entry: &'a PasswordEntry,
+    /// Names of the broken policies with the reasons.
+    pub violations: Vec<(&'a str, String)>,
+}
+
+impl Report<'_> {
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl fmt::Display for Report<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}: ", self.entry.line, self.entry)?;
+        if self.is_valid() {
+            return write!(f, "valid");
+        }
+        let violations: Vec<_> = self
+            .violations
+            .iter()
+            .map(|(name, reason)| format!("{} ({})", name, reason))
+            .collect();
+        write!(f, "invalid, {}", violations.join("; "))
+    }
+}
+
+/// Reports every entry against all the `policies`.
+pub fn check<'a>(
+    entries: &'a [PasswordEntry],
+    policies: &'a [Box<dyn PasswordPolicy>],
+) -> Vec<Report<'a>> {
+    entries
+        .iter()
+        .map(|entry| Report {
+            entry,
+            violations: policies
+                .iter()
+                .filter_map(|policy| Some((policy.name(), policy.violation(entry)?)))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Number of entries following `policy`.
+pub fn count_valid(entries: &[PasswordEntry], policy: &dyn PasswordPolicy) -> usize {
+    entries
+        .iter()
+        .filter(|entry| policy.is_valid(entry))
+        .count()
+}
+
+pub fn part1(entries: &[PasswordEntry]) -> usize {
+    count_valid(entries, &policy::Count)
+}
+
+pub fn part2(entries: &[PasswordEntry]) -> usize {
+    count_valid(entries, &policy::Position(Indexing::Char))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<PasswordEntry>;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input, mode)?)
+    }
+
+    fn part1(entries: &Self::Input) -> Result<String> {
+        Ok(part1(entries).to_string())
+    }
+
+    fn part2(entries: &Self::Input) -> Result<String> {
+        Ok(part2(entries).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n";
+
+    #[test]
+    fn parse_entries() {
+        let entries = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        assert_eq!(
+            entries[0],
+            PasswordEntry {
+                line: 1,
+                low: 1,
+                high: 3,
+                letter: "a".to_owned(),
+                password: "abcde".to_owned()
+            }
+        );
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2].to_string(), "2-9 c: ccccccccc");
+    }
+
+    #[test]
+    fn malformed_entries() {
+        let input = "1-3 a: abcde\n1-x b: cdefg\n2-9 c ccccccccc\n";
+        let entries = parse_input(input, Mode::Lenient).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].line, 1);
+        let err = parse_input(input, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.expected, "number");
+
+        let err = parse_input("2-9 c ccccccccc", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 16));
+        assert_eq!(err.expected, "\":\"");
+    }
+
+    #[test]
+    fn invalid_bounds() {
+        let input = "0-3 a: abc\n3-1 a: abc\n1-3 ab: abc\n2-2 é: éé\n";
+        let entries = parse_input(input, Mode::Lenient).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].line, 4);
+        let lines: Vec<_> = input.lines().collect();
+        let errors: Vec<_> = lines
+            .iter()
+            .map(|line| parse_input(line, Mode::Strict).unwrap_err())
+            .map(|err| (err.column, err.expected))
+            .take(3)
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (1, "position from 1".to_owned()),
+                (3, "number at least 3".to_owned()),
+                (6, "\":\"".to_owned())
+            ]
+        );
+    }
+
+    fn clusters(s: &str) -> Vec<&str> {
+        s.graphemes(true).collect()
+    }
+
+    #[test]
+    fn grapheme_clusters() {
+        assert_eq!(clusters("e\u{301}a"), vec!["e\u{301}", "a"]);
+        assert_eq!(clusters("a\r\nb"), vec!["a", "\r\n", "b"]);
+        // a Tamil spacing mark and a joiner without a pictograph before it
+        assert_eq!(clusters("\u{ba8}\u{bbf}x"), vec!["\u{ba8}\u{bbf}", "x"]);
+        assert_eq!(
+            clusters("a\u{200d}\u{1f600}"),
+            vec!["a\u{200d}", "\u{1f600}"]
+        );
+        assert!(Indexing::Grapheme.letter_at("\u{ba8}\u{bbf}x", 2, "x"));
+        assert_eq!(
+            clusters("\u{1f1e8}\u{1f1ff}\u{1f1fa}\u{1f1f8}\u{1f1eb}"),
+            vec!["\u{1f1e8}\u{1f1ff}", "\u{1f1fa}\u{1f1f8}", "\u{1f1eb}"]
+        );
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        assert_eq!(clusters(family), vec![family]);
+        assert_eq!(
+            clusters("\u{1f44d}\u{1f3fd}!"),
+            vec!["\u{1f44d}\u{1f3fd}", "!"]
+        );
+        assert_eq!(
+            clusters("\u{1100}\u{1161}\u{11a8}\u{ac00}"),
+            vec!["\u{1100}\u{1161}\u{11a8}", "\u{ac00}"]
+        );
+        assert_eq!(clusters(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn indexing() {
+        let s = "e\u{301}a";
+        assert!(Indexing::Grapheme.letter_at(s, 2, "a"));
+        assert!(Indexing::Char.letter_at(s, 3, "a"));
+        assert!(!Indexing::Char.letter_at(s, 2, "a"));
+        assert!(Indexing::Byte.letter_at(s, 4, "a"));
+        assert!(!Indexing::Byte.letter_at(s, 2, "a"));
+        assert!(Indexing::Grapheme.letter_at(s, 1, "e\u{301}"));
+        assert!(!Indexing::Grapheme.letter_at(s, 1, "e"));
+        assert!(Indexing::Char.letter_at(s, 1, "e"));
+        for &indexing in &[Indexing::Byte, Indexing::Char, Indexing::Grapheme] {
+            assert!(!indexing.letter_at(s, 0, "e"));
+            assert!(!indexing.letter_at(s, 9, "a"));
+        }
+
+        let entries = parse_input("1-3 é: éxé\n3-4 x: éxé\n", Mode::Strict).unwrap();
+        let valid = |policy: &str| count_valid(&entries, parse_policy(policy).unwrap().as_ref());
+        assert_eq!(valid("count"), 1);
+        assert_eq!(valid("position"), 0);
+        assert_eq!(valid("position:grapheme"), 0);
+        // é takes the first two bytes, so x is the third one
+        assert_eq!(valid("position:byte"), 2);
+        assert!(!policy::Position(Indexing::Char).is_valid(&entries[1]));
+        assert!(parse_policy("position:word").is_err());
+    }
+
+    #[test]
+    fn count_policy() {
+        let entries = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        assert_eq!(part1(&entries[..1]), 1);
+        assert_eq!(part1(&entries[1..2]), 0);
+        assert_eq!(part1(&entries[2..]), 1);
+    }
+
+    #[test]
+    fn position_policy() {
+        let entries = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        assert_eq!(part2(&entries[..1]), 1);
+        assert_eq!(part2(&entries[1..2]), 0);
+        assert_eq!(part2(&entries[2..]), 0);
+    }
+
+    fn reasons(policy: &str, passwords: &[&str]) -> Vec<Option<String>> {
+        let policy = parse_policy(policy).unwrap();
+        passwords
+            .iter()
+            .map(|password| {
+                let entry = parse_input(&format!("1-3 a: {}", password), Mode::Strict).unwrap();
+                policy.violation(&entry[0])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports() {
+        let entries = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let policies = vec![
+            parse_policy("count").unwrap(),
+            parse_policy("position").unwrap(),
+        ];
+        let reports: Vec<_> = check(&entries, &policies)
+            .iter()
+            .map(Report::to_string)
+            .collect();
+        assert_eq!(
+            reports,
+            vec![
+                "line 1: 1-3 a: abcde: valid",
+                "line 2: 1-3 b: cdefg: invalid, count ('b' appears 0 times, expected 1 to 3); \
+                 position ('b' is at neither 1 nor 3)",
+                "line 3: 2-9 c: ccccccccc: invalid, position ('c' is at both 2 and 9)",
+            ]
+        );
+    }
+
+    #[test]
+    fn other_policies() {
+        assert_eq!(
+            reasons(
+                "regex:^[a-z]+\\d{2,}$",
+                &["abc12", "abc1", "Abc123", "x999"]
+            ),
+            vec![
+                None,
+                Some("doesn't match /^[a-z]+\\d{2,}$/".to_owned()),
+                Some("doesn't match /^[a-z]+\\d{2,}$/".to_owned()),
+                None
+            ]
+        );
+        assert_eq!(
+            reasons("forbidden:123,pass,", &["hunter2", "password123"]),
+            vec![None, Some("contains \"123\", \"pass\"".to_owned())]
+        );
+        assert_eq!(
+            reasons("entropy:8", &["abcdefgh", "aaaaaaaa"]),
+            vec![
+                None,
+                Some("has 0.00 bits of entropy, expected 8".to_owned())
+            ]
+        );
+        assert!((policy::entropy("aabb") - 4.0).abs() < 1e-9);
+        assert_eq!(policy::entropy(""), 0.0);
+        assert!(parse_policy("regex").is_err());
+        assert!(parse_policy("regex:(a").is_err());
+        // matched in linear time, without recursing per character
+        let slow = format!("{}b", "a".repeat(30));
+        assert!(reasons("regex:^(a|a)*$", &[&slow])[0].is_some());
+        assert!(reasons("regex:a*", &[&"a".repeat(200_000)])[0].is_none());
+        assert!(parse_policy("count:1").is_err());
+        assert!(parse_policy("entropy:lots").is_err());
+        assert!(parse_policy("strength").is_err());
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(Day2::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(), "2");
+        assert_eq!(Day2::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(), "1");
+    }
+}
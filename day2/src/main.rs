@@ -1,57 +1,57 @@
-use std::{env::args, fs::read_to_string};
+use aoc_common::{print_json, Cli, Format, Opt, Result};
+use day2::{check, parse_policy, part1, part2, Day2};
 
-fn split_at_once(input: &str, sep: char) -> Option<(&str, &str)> {
-    let mut s = input.splitn(2, sep);
-    s.next().and_then(|n| s.next().map(|v| (n, v)))
-}
+const USAGE: &str = "\
+usage: day2 [<input>|-] [--policy <policy>]... [--invalid]
+            [--format <human|json>] [--strict]
 
-fn parse_input(input: &str) -> impl Iterator<Item = (usize, usize, char, &str)> {
-    input
-        .lines()
-        .filter_map(|l| {
-            split_at_once(l, '-').and_then(|(min, rest)| {
-                split_at_once(rest, ' ')
-                    .map(|(max, rest)| (min, max, rest))
-                    .and_then(|(min, max, rest)| {
-                        split_at_once(rest, ':').map(|(c, rest)| (min, max, c, rest.trim()))
-                    })
-            })
-        })
-        .filter_map(|(min, max, c, s)| {
-            Some((
-                min.parse::<usize>().ok()?,
-                max.parse::<usize>().ok()?,
-                c.chars().next()?,
-                s,
-            ))
-        })
-}
+Every --policy checks each password, reporting for every line which
+policies it breaks and why. A policy is one of:
 
-fn part1(input: &str) -> usize {
-    parse_input(input)
-        .map(|(min, max, c, s)| (min, max, s.chars().filter(|&ch| ch == c).count()))
-        .filter(|(min, max, count)| min <= count && count <= max)
-        .count()
-}
+  count                          the letter appears low to high times
+  position[:<indexing>]          the letter is at exactly one of the positions
+  regex:<pattern>                the password matches the pattern
+  forbidden:<substring>[,...]    the password contains none of the substrings
+  entropy:<bits>                 the password has at least <bits> of entropy
 
-fn part2(input: &str) -> usize {
-    parse_input(input)
-        .filter(|(min, max, c, s)| {
-            let mut chars = s.chars();
-            let c1 = chars.nth(min - 1);
-            let c2 = chars.nth(max - min - 1);
-            (c1 == Some(*c)) ^ (c2 == Some(*c))
-        })
-        .count()
-}
+Positions count chars unless <indexing> is byte or grapheme, for letters
+made of several chars, like an accent mark. Both numbers of an entry are
+checked when parsing: the lower one is at least 1, the higher one at least
+the lower one.
+
+--invalid reports only the lines breaking some policy.";
+
+const OPTIONS: &[Opt] = &[Opt::value("--policy"), Opt::flag("--invalid")];
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input = read_to_string(args().nth(1).ok_or("No input file")?)?;
+    let cli = Cli::from_env_with(USAGE, OPTIONS)?;
+    let input = cli.input(2)?;
+    if cli.format == Format::Json {
+        print_json::<Day2>(2, &input, cli.mode);
+        return Ok(());
+    }
+    let policies = cli
+        .values("--policy")
+        .map(parse_policy)
+        .collect::<Result<Vec<_>>>()?;
+    let entries = input.parse::<Day2>(cli.mode)?;
+
+    if policies.is_empty() {
+        let len1 = part1(&entries);
+        let len2 = part2(&entries);
+        println!("part 1: {}", len1);
+        println!("part 2: {}", len2);
+        return Ok(());
+    }
 
-    let len1 = part1(&input);
-    let len2 = part2(&input);
-    println!("part 1: {}", len1);
-    println!("part 2: {}", len2);
+    let reports = check(&entries, &policies);
+    for report in &reports {
+        if !(cli.flag("--invalid") && report.is_valid()) {
+            println!("{}", report);
+        }
+    }
+    let valid = reports.iter().filter(|report| report.is_valid()).count();
+    println!("{} of {} passwords valid", valid, reports.len());
 
     Ok(())
 }
@@ -0,0 +1,218 @@
+use crate::PasswordEntry;
+use aoc_common::{Error, Result};
+use regex::Regex;
+use std::{collections::HashMap, str::FromStr};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Rule a password has to follow.
+pub trait PasswordPolicy {
+    /// Name the policy is chosen by.
+    fn name(&self) -> &str;
+
+    /// Why `entry` breaks the policy, if it does.
+    fn violation(&self, entry: &PasswordEntry) -> Option<String>;
+
+    fn is_valid(&self, entry: &PasswordEntry) -> bool {
+        self.violation(entry).is_none()
+    }
+}
+
+/// The letter appears between the lowest and highest number of times.
+#[derive(Debug, Clone, Copy)]
+pub struct Count;
+
+impl PasswordPolicy for Count {
+    fn name(&self) -> &str {
+        "count"
+    }
+
+    fn violation(&self, entry: &PasswordEntry) -> Option<String> {
+        let count = entry
+            .password
+            .graphemes(true)
+            .filter(|&c| c == entry.letter)
+            .count();
+        if entry.low <= count && count <= entry.high {
+            return None;
+        }
+        Some(format!(
+            "'{}' appears {} times, expected {} to {}",
+            entry.letter, count, entry.low, entry.high
+        ))
+    }
+}
+
+/// What the positions of [`Position`] count.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Indexing {
+    /// Bytes of the UTF-8 encoding, the letter has to start at the position.
+    Byte,
+    /// Unicode scalar values, the letter has to start at the position.
+    Char,
+    /// Grapheme clusters, the one at the position has to be the letter.
+    Grapheme,
+}
+
+impl Indexing {
+    /// Whether `letter` is at `position` of `s`, counted from 1.
+    pub fn letter_at(self, s: &str, position: usize, letter: &str) -> bool {
+        let i = match position.checked_sub(1) {
+            Some(i) => i,
+            None => return false,
+        };
+        match self {
+            Indexing::Byte => s
+                .as_bytes()
+                .get(i..)
+                .is_some_and(|rest| rest.starts_with(letter.as_bytes())),
+            Indexing::Char => s
+                .char_indices()
+                .nth(i)
+                .is_some_and(|(offset, _)| s[offset..].starts_with(letter)),
+            Indexing::Grapheme => s.graphemes(true).nth(i) == Some(letter),
+        }
+    }
+}
+
+impl FromStr for Indexing {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "byte" => Ok(Indexing::Byte),
+            "char" => Ok(Indexing::Char),
+            "grapheme" => Ok(Indexing::Grapheme),
+            _ => Err(format!("Invalid indexing: {}", s).into()),
+        }
+    }
+}
+
+/// The letter is at exactly one of the two positions, counted from 1.
+#[derive(Debug, Clone, Copy)]
+pub struct Position(pub Indexing);
+
+impl PasswordPolicy for Position {
+    fn name(&self) -> &str {
+        "position"
+    }
+
+    fn violation(&self, entry: &PasswordEntry) -> Option<String> {
+        let at = |position| self.0.letter_at(&entry.password, position, &entry.letter);
+        let (low, high) = (entry.low, entry.high);
+        match (at(low), at(high)) {
+            (true, true) => Some(format!(
+                "'{}' is at both {} and {}",
+                entry.letter, low, high
+            )),
+            (false, false) => Some(format!(
+                "'{}' is at neither {} nor {}",
+                entry.letter, low, high
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// The password matches a regular expression.
+#[derive(Debug, Clone)]
+pub struct Pattern(pub Regex);
+
+impl PasswordPolicy for Pattern {
+    fn name(&self) -> &str {
+        "regex"
+    }
+
+    fn violation(&self, entry: &PasswordEntry) -> Option<String> {
+        if self.0.is_match(&entry.password) {
+            return None;
+        }
+        Some(format!("doesn't match /{}/", self.0))
+    }
+}
+
+/// The password contains none of the substrings.
+#[derive(Debug, Clone)]
+pub struct Forbidden(pub Vec<String>);
+
+impl PasswordPolicy for Forbidden {
+    fn name(&self) -> &str {
+        "forbidden"
+    }
+
+    fn violation(&self, entry: &PasswordEntry) -> Option<String> {
+        let found: Vec<_> = self
+            .0
+            .iter()
+            .filter(|forbidden| entry.password.contains(forbidden.as_str()))
+            .map(|forbidden| format!("{:?}", forbidden))
+            .collect();
+        if found.is_empty() {
+            return None;
+        }
+        Some(format!("contains {}", found.join(", ")))
+    }
+}
+
+/// The password carries at least this many bits of Shannon entropy.
+#[derive(Debug, Clone, Copy)]
+pub struct Entropy(pub f64);
+
+/// Bits of Shannon entropy of the characters of `s`, all of them together.
+pub fn entropy(s: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    let len: usize = counts.values().sum();
+    counts
+        .values()
+        .map(|&count| count as f64 * (len as f64 / count as f64).log2())
+        .sum()
+}
+
+impl PasswordPolicy for Entropy {
+    fn name(&self) -> &str {
+        "entropy"
+    }
+
+    fn violation(&self, entry: &PasswordEntry) -> Option<String> {
+        let bits = entropy(&entry.password);
+        if bits >= self.0 {
+            return None;
+        }
+        Some(format!(
+            "has {:.2} bits of entropy, expected {}",
+            bits, self.0
+        ))
+    }
+}
+
+/// Parses a policy by its name: `count`, `position[:<byte|char|grapheme>]`
+/// (char by default), `regex:<pattern>`,
+/// `forbidden:<substring>[,<substring>...]` or `entropy:<bits>`.
+pub fn parse_policy(s: &str) -> Result<Box<dyn PasswordPolicy>> {
+    let (name, argument) = match s.split_once(':') {
+        Some((name, argument)) => (name, Some(argument)),
+        None => (s, None),
+    };
+    Ok(match (name, argument) {
+        ("count", None) => Box::new(Count),
+        ("position", None) => Box::new(Position(Indexing::Char)),
+        ("position", Some(indexing)) => Box::new(Position(indexing.parse()?)),
+        ("regex", Some(pattern)) => Box::new(Pattern(
+            Regex::new(pattern).map_err(|e| format!("Invalid pattern {}: {}", pattern, e))?,
+        )),
+        ("forbidden", Some(substrings)) => Box::new(Forbidden(
+            substrings
+                .split(',')
+                .filter(|substring| !substring.is_empty())
+                .map(str::to_owned)
+                .collect(),
+        )),
+        ("entropy", Some(bits)) => Box::new(Entropy(
+            bits.parse()
+                .map_err(|_| format!("Invalid number of bits: {}", bits))?,
+        )),
+        _ => return Err(format!("Invalid policy: {}", s).into()),
+    })
+}
@@ -0,0 +1,230 @@
+use aoc_common::{Diagnostic, Error, Grid, Mode, Result, Solution};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Tile {
+    #[default]
+    Empty,
+    Tree,
+}
+
+/// Slope of `x` rows down and `y` columns right per step.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct Move {
+    x: usize,
+    y: usize,
+}
+
+/// Slopes multiplied in the second part.
+pub const SLOPES: [(usize, usize); 5] = [(1, 1), (1, 3), (1, 5), (1, 7), (2, 1)];
+
+impl Move {
+    pub fn new(x: usize, y: usize) -> Result<Self> {
+        if x == 0 {
+            return Err(format!("Invalid slope: {}:0, it has to go down", y).into());
+        }
+        Ok(Move { x, y })
+    }
+
+    pub fn down(&self) -> usize {
+        self.x
+    }
+
+    pub fn right(&self) -> usize {
+        self.y
+    }
+}
+
+impl FromStr for Move {
+    type Err = Error;
+
+    /// Parses `<right>:<down>`, e.g. `3:1`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (right, down) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid slope: {}, expected <right>:<down>", s))?;
+        Move::new(down.parse()?, right.parse()?)
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "right {}, down {}", self.y, self.x)
+    }
+}
+
+pub type Map = Grid<Tile>;
+
+pub fn parse_input(input: &str, mode: Mode) -> Result<Map, Diagnostic> {
+    Grid::parse(input, "'.' or '#'", mode, |c| match c {
+        '.' => Some(Tile::Empty),
+        '#' => Some(Tile::Tree),
+        _ => None,
+    })
+}
+
+/// Positions `(column, row)` visited from the top left corner down to the
+/// bottom, columns keep growing past the right edge.
+pub fn path(map: &Map, moves: &Move) -> impl Iterator<Item = (usize, usize)> {
+    let right = moves.y;
+    (0..map.height())
+        .step_by(moves.x)
+        .enumerate()
+        .map(move |(step, row)| (step * right, row))
+}
+
+/// Counts trees hit going `moves.x` rows down and `moves.y` columns right,
+/// the map repeats to the right.
+pub fn solve(map: &Map, moves: &Move) -> i64 {
+    path(map, moves)
+        .filter(|&(column, row)| *map.wrapping(column as isize, row as isize) == Tile::Tree)
+        .count() as i64
+}
+
+/// Slope with the fewest trees among those going at most `bound` columns
+/// right and rows down, the first one found on ties.
+pub fn min_trees(map: &Map, bound: usize) -> Option<(Move, i64)> {
+    (1..=bound)
+        .flat_map(|down| (0..=bound).map(move |right| Move { x: down, y: right }))
+        .map(|moves| (moves, solve(map, &moves)))
+        .min_by_key(|&(_, trees)| trees)
+}
+
+/// Draws the map repeated to the right as far as the path goes, marking
+/// the squares visited with `O`, or `X` on a tree, like the puzzle does.
+///
+/// The starting square isn't marked, as in the puzzle.
+pub fn render_path(map: &Map, moves: &Move) -> String {
+    let end = path(map, moves).last().map_or(0, |(column, _)| column);
+    let width = (end / map.width().max(1) + 1) * map.width();
+    let mut tiles = Grid::filled(width, map.height(), '.');
+    for (column, row) in tiles.positions() {
+        if *map.wrapping(column as isize, row as isize) == Tile::Tree {
+            tiles[(column, row)] = '#';
+        }
+    }
+    for pos in path(map, moves).skip(1) {
+        tiles[pos] = if tiles[pos] == '#' { 'X' } else { 'O' };
+    }
+    tiles.render(|&c| c)
+}
+
+pub fn part1(map: &Map) -> i64 {
+    solve(map, &Move { x: 1, y: 3 })
+}
+
+pub fn part2(map: &Map) -> i64 {
+    SLOPES
+        .iter()
+        .map(|&(x, y)| solve(map, &Move { x, y }))
+        .product()
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Map;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input, mode)?)
+    }
+
+    fn part1(map: &Self::Input) -> Result<String> {
+        Ok(part1(map).to_string())
+    }
+
+    fn part2(map: &Self::Input) -> Result<String> {
+        Ok(part2(map).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "\
+..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#
+";
+
+    #[test]
+    fn trees_per_slope() {
+        let map = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        assert_eq!(solve(&map, &Move::new(1, 1).unwrap()), 2);
+        assert_eq!(solve(&map, &Move::new(1, 3).unwrap()), 7);
+        assert_eq!(solve(&map, &Move::new(1, 5).unwrap()), 3);
+        assert_eq!(solve(&map, &Move::new(1, 7).unwrap()), 4);
+        assert_eq!(solve(&map, &Move::new(2, 1).unwrap()), 2);
+    }
+
+    #[test]
+    fn slopes() {
+        let moves: Move = "3:1".parse().unwrap();
+        assert_eq!(moves, Move::new(1, 3).unwrap());
+        assert_eq!((moves.right(), moves.down()), (3, 1));
+        assert_eq!(moves.to_string(), "right 3, down 1");
+        assert!("3:0".parse::<Move>().is_err());
+        assert!(Move::new(0, 3).is_err());
+        assert!("3".parse::<Move>().is_err());
+        assert!("a:1".parse::<Move>().is_err());
+    }
+
+    #[test]
+    fn fewest_trees() {
+        let map = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let (moves, trees) = min_trees(&map, 3).unwrap();
+        assert_eq!(trees, solve(&map, &moves));
+        for down in 1..=3 {
+            for right in 0..=3 {
+                assert!(solve(&map, &Move::new(down, right).unwrap()) >= trees);
+            }
+        }
+        assert_eq!((moves, trees), (Move::new(3, 1).unwrap(), 0));
+        assert_eq!(min_trees(&map, 0), None);
+    }
+
+    #[test]
+    fn rendered_path() {
+        let map = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let rendered = render_path(&map, &Move::new(1, 3).unwrap());
+        let lines: Vec<_> = rendered.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert_eq!(lines[0], "..##.........##.........##.......");
+        assert_eq!(lines[1], "#..O#...#..#...#...#..#...#...#..");
+        assert_eq!(lines[2], ".#....X..#..#....#..#..#....#..#.");
+        assert_eq!(lines[10], ".#..#...#.#.#..#...#.#.#..#...X.#");
+        assert_eq!(rendered.matches('X').count(), 7);
+        assert_eq!(rendered.matches('O').count(), 3);
+
+        let straight = render_path(&map, &Move::new(2, 0).unwrap());
+        assert_eq!(straight.lines().next().unwrap().len(), 11);
+        assert_eq!(straight.matches(['O', 'X']).count(), 5);
+    }
+
+    #[test]
+    fn invalid_tile() {
+        let err = parse_input("..#\n.O.\n", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let map = parse_input("..#\n.O.\n", Mode::Lenient).unwrap();
+        assert_eq!(map[(1, 1)], Tile::Empty);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(Day3::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(), "7");
+        assert_eq!(
+            Day3::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(),
+            "336"
+        );
+    }
+}
@@ -1,62 +1,68 @@
-use std::{env::args, fs::read_to_string};
+use aoc_common::{print_json, Cli, Format, Opt, Result};
+use day3::{min_trees, part1, part2, render_path, solve, Day3, Move};
 
-#[derive(Debug, Eq, PartialEq)]
-enum Tile {
-    Empty,
-    Tree,
-}
-
-struct Move {
-    x: usize,
-    y: usize,
-}
-
-impl Move {
-    fn new(x: usize, y: usize) -> Self {
-        Move { x, y }
-    }
-}
+const USAGE: &str = "\
+usage: day3 [<input>|-] [--slopes <right>:<down>[,...]] [--search <bound>]
+            [--render <right>:<down>] [--format <human|json>] [--strict]
 
-type Map = Vec<Vec<Tile>>;
+--slopes counts the trees of every slope and their product, --search finds
+the slope with the fewest trees going at most <bound> right and down, and
+--render draws the path of a slope marked with O and X.";
 
-fn parse_input(input: &str) -> Map {
-    input.trim().chars().fold(vec![Vec::new()], |mut acc, c| {
-        match c {
-            '.' => acc.last_mut().unwrap().push(Tile::Empty),
-            '#' => acc.last_mut().unwrap().push(Tile::Tree),
-            '\n' => acc.push(Vec::new()),
-            _ => (),
-        }
-        acc
-    })
-}
-
-fn solve(map: &Map, moves: &Move) -> i64 {
-    let map_width = map[0].len();
-    (0..map.len())
-        .step_by(moves.x)
-        .fold((0, 0), |(trees, y), x| match map[x][y] {
-            Tile::Tree => (trees + 1, (y + moves.y) % map_width),
-            Tile::Empty => (trees, (y + moves.y) % map_width),
-        })
-        .0
-}
+const OPTIONS: &[Opt] = &[
+    Opt::value("--slopes"),
+    Opt::value("--search"),
+    Opt::value("--render"),
+];
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input = read_to_string(args().nth(1).ok_or("No input file")?)?;
-    let map = parse_input(&input);
-    let moves = vec![
-        Move::new(1, 1),
-        Move::new(1, 3),
-        Move::new(1, 5),
-        Move::new(1, 7),
-        Move::new(2, 1),
-    ];
+    let cli = Cli::from_env_with(USAGE, OPTIONS)?;
+    let input = cli.input(3)?;
+    if cli.format == Format::Json {
+        print_json::<Day3>(3, &input, cli.mode);
+        return Ok(());
+    }
+    let slopes = cli
+        .value("--slopes")
+        .map(|slopes| {
+            slopes
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<Vec<Move>>>()
+        })
+        .transpose()?;
+    let search = cli.value("--search").map(str::parse).transpose()?;
+    let render = cli.value("--render").map(str::parse::<Move>).transpose()?;
+    let map = input.parse::<Day3>(cli.mode)?;
 
-    let result1 = solve(&map, &Move { x: 1, y: 3 });
-    println!("part1: {}", result1);
+    if cli.options.is_empty() {
+        let result1 = part1(&map);
+        println!("part1: {}", result1);
 
-    let result2: i64 = moves.iter().map(|mov| solve(&map, mov)).product();
-    println!("part2: {}", result2);
+        let result2 = part2(&map);
+        println!("part2: {}", result2);
+        return Ok(());
+    }
+    if let Some(slopes) = &slopes {
+        let mut product = Some(1i64);
+        for moves in slopes {
+            let trees = solve(&map, moves);
+            println!("{}: {}", moves, trees);
+            product = product.and_then(|product| product.checked_mul(trees));
+        }
+        match product {
+            Some(product) => println!("product: {}", product),
+            None => println!("product: overflow"),
+        }
+    }
+    if let Some(bound) = search {
+        match min_trees(&map, bound) {
+            Some((moves, trees)) => println!("fewest trees: {} with {}", trees, moves),
+            None => println!("no slope goes down at most {}", bound),
+        }
+    }
+    if let Some(moves) = &render {
+        print!("{}", render_path(&map, moves));
+    }
     Ok(())
 }
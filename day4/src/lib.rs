@@ -0,0 +1,284 @@
+use aoc_common::{collect, sections, Diagnostic, Mode, Result, Solution, Source};
+use std::sync::OnceLock;
+
+pub mod schema;
+
+pub use schema::{Schema, Violation};
+
+/// Schema of passports, checked by the second part.
+pub const PASSPORT: &str = include_str!("../passport.schema");
+
+/// A `key:value` field with the position of its value.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Field {
+    pub name: String,
+    pub value: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// A record of fields, like a passport.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Document {
+    /// Line the record starts on.
+    pub line: usize,
+    pub fields: Vec<Field>,
+}
+
+impl Document {
+    /// The field called `name`, the last one if repeated.
+    pub fn get(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().rev().find(|field| field.name == name)
+    }
+}
+
+fn parse_document<'a>(
+    source: &Source<'a>,
+    record: &'a str,
+    mode: Mode,
+) -> Result<Document, Diagnostic> {
+    let mut fields = Vec::new();
+    for field in record.split_whitespace() {
+        let (name, value) = match source.split(field, ":") {
+            Ok(field) => field,
+            Err(_) if mode == Mode::Lenient => continue,
+            Err(e) => return Err(e),
+        };
+        let (line, column) = source.position(value);
+        fields.push(Field {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            line,
+            column,
+        });
+    }
+    Ok(Document {
+        line: source.position(record).0,
+        fields,
+    })
+}
+
+/// Parses records separated by blank lines.
+pub fn parse_input(input: &str, mode: Mode) -> Result<Vec<Document>, Diagnostic> {
+    let source = Source::new(input);
+    let documents = sections(input).map(|record| parse_document(&source, record, mode));
+    collect(documents, mode)
+}
+
+/// [`PASSPORT`], parsed on first use.
+pub fn passport_schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(|| PASSPORT.parse().expect("passport schema is valid"))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Document>;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input, mode)?)
+    }
+
+    fn part1(documents: &Self::Input) -> Result<String> {
+        let schema = passport_schema();
+        let complete = documents.iter().filter(|d| schema.has_required(d));
+        Ok(complete.count().to_string())
+    }
+
+    fn part2(documents: &Self::Input) -> Result<String> {
+        let schema = passport_schema();
+        Ok(documents
+            .iter()
+            .filter(|d| schema.is_valid(d))
+            .count()
+            .to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "\
+ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in
+";
+
+    const INVALID: &str = "\
+eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007
+";
+
+    const VALID: &str = "\
+pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719
+";
+
+    #[test]
+    fn required_fields() {
+        assert_eq!(Day4::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(), "2");
+    }
+
+    #[test]
+    fn malformed_fields() {
+        let input = "byr:1937 iyr:2017 eyr:2020 hgt:183cm\nhcl:#fffffd ecl:gry pid:860033327 cid\n";
+        assert_eq!(parse_input(input, Mode::Lenient).unwrap().len(), 1);
+        let err = parse_input(input, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 38));
+
+        let input = input.replace("byr:1937", "byr:19x7").replace(" cid", "");
+        let documents = parse_input(&input, Mode::Strict).unwrap();
+        let schema = passport_schema();
+        assert!(schema.has_required(&documents[0]));
+        assert_eq!(
+            schema.check(&documents[0]),
+            vec![Violation {
+                line: 1,
+                column: 5,
+                message: "byr: expected year from 1920 to 2002, found \"19x7\"".to_owned()
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_passpords() {
+        let passpords = parse_input(INVALID, Mode::Strict).unwrap();
+        let schema = passport_schema();
+        assert_eq!(passpords.len(), 4);
+        assert!(passpords.iter().all(|p| schema.has_required(p)));
+        assert!(passpords.iter().all(|p| !schema.is_valid(p)));
+        assert!(std::ptr::eq(schema, passport_schema()));
+    }
+
+    #[test]
+    fn valid_passpords() {
+        let passpords = parse_input(VALID, Mode::Strict).unwrap();
+        let schema = passport_schema();
+        assert_eq!(passpords.len(), 4);
+        assert!(passpords.iter().all(|p| schema.is_valid(p)));
+    }
+
+    #[test]
+    fn violations() {
+        let documents = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        let schema = passport_schema();
+        let report: Vec<_> = schema
+            .check(&documents[1])
+            .iter()
+            .map(Violation::to_string)
+            .collect();
+        assert_eq!(report, vec!["line 4, column 1: missing hgt"]);
+
+        let documents = parse_input(INVALID, Mode::Strict).unwrap();
+        let report: Vec<_> = schema
+            .check(&documents[0])
+            .iter()
+            .map(Violation::to_string)
+            .collect();
+        assert_eq!(
+            report,
+            vec![
+                "line 1, column 5: eyr: expected year from 2020 to 2030, found \"1972\"",
+                "line 2, column 25: hgt: expected measure of 150 to 193cm or 59 to 76in, found \"170\"",
+                "line 2, column 33: pid: expected 9 digits, found \"186cm\"",
+            ]
+        );
+    }
+
+    const TICKET: &str = "\
+# train tickets
+from required enum prague brno
+to required enum prague brno ostrava
+seat optional regex [0-9]+[a-d]
+class optional digits 1
+";
+
+    #[test]
+    fn other_documents() {
+        let schema: Schema = TICKET.parse().unwrap();
+        let tickets = parse_input(
+            "from:brno to:ostrava seat:12c\n\nfrom:brno\nseat:12e class:3",
+            Mode::Strict,
+        )
+        .unwrap();
+        assert!(schema.is_valid(&tickets[0]));
+        let report: Vec<_> = schema
+            .check(&tickets[1])
+            .iter()
+            .map(Violation::to_string)
+            .collect();
+        assert_eq!(
+            report,
+            vec![
+                "line 3, column 1: missing to",
+                "line 4, column 6: seat: expected value matching /[0-9]+[a-d]/, found \"12e\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_schemas() {
+        let error = |schema: &str| match schema.parse::<Schema>() {
+            Ok(_) => panic!("{} parsed", schema),
+            Err(e) => e.to_string(),
+        };
+        assert_eq!(
+            error("byr required year 2002-1920"),
+            "line 1, column 19: expected range from at most 1920, found \"2002-1920\""
+        );
+        assert_eq!(
+            error("\nbyr mandatory year 1920-2002"),
+            "line 2, column 5: expected required or optional, found \"mandatory year 1920-2002\""
+        );
+        assert_eq!(
+            error("hgt required measure cm"),
+            "line 1, column 24: expected \":\", found end of line"
+        );
+        assert_eq!(error("hcl required colour"), "line 1, column 14: expected year, measure, color, enum, digits, regex or any, found \"colour\"");
+        assert_eq!(
+            error("pid required digits 9 10"),
+            "line 1, column 23: expected end of line, found \"10\""
+        );
+        assert_eq!(
+            error("pid required any\npid optional any"),
+            "line 2, column 1: expected field without a rule yet, found \"pid optional any\""
+        );
+        assert!(error("x required regex (a")
+            .starts_with("line 1, column 18: expected regular expression"));
+        assert!("".parse::<Schema>().unwrap().rules.is_empty());
+    }
+}
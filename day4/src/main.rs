@@ -1,82 +1,60 @@
-use std::{collections::HashMap, env::args, fs::read_to_string};
+use aoc_common::{print_json, Cli, Error, Format, Opt, Result};
+use day4::{passport_schema, Day4, Schema};
+use std::fs;
 
-fn split_at_once(input: &str, sep: char) -> Option<(&str, &str)> {
-    let mut s = input.splitn(2, sep);
-    s.next().and_then(|n| s.next().map(|v| (n, v)))
-}
+const USAGE: &str = "\
+usage: day4 [<input>|-] [--schema <file>] [--report] [--format <human|json>] [--strict]
 
-struct Passpord<'a> {
-    eyr: i32,
-    byr: i32,
-    iyr: i32,
-    hgt: (i32, &'a str),
-    ecl: &'a str,
-    hcl: &'a str,
-    pid: &'a str,
-    #[allow(unused)]
-    cid: Option<&'a str>,
-}
+--schema checks the records against the field rules of <file> instead of the
+passport ones, one rule per line:
 
-fn parse_input(input: &str) -> impl Iterator<Item = Passpord> {
-    input
-        .split("\n\n")
-        .map(|record| {
-            record
-                .split_whitespace()
-                .filter_map(|field| split_at_once(field, ':'))
-                .collect::<HashMap<_, _>>()
-        })
-        .filter_map(|passpord| {
-            let hgt = passpord.get("hgt")?;
-            let unit_pos = hgt
-                .chars()
-                .position(|c| c.is_alphabetic())
-                .unwrap_or_else(|| hgt.len());
-            let (val, unit) = passpord.get("hgt")?.split_at(unit_pos);
-            let height = (val.parse().ok()?, unit);
-            Some(Passpord {
-                eyr: passpord.get("eyr")?.parse().ok()?,
-                byr: passpord.get("byr")?.parse().ok()?,
-                iyr: passpord.get("iyr")?.parse().ok()?,
-                ecl: passpord.get("ecl")?,
-                hgt: height,
-                hcl: passpord.get("hcl")?,
-                pid: passpord.get("pid")?,
-                cid: passpord.get("cid").copied(),
-            })
-        })
-}
+  <field> <required|optional> <type> [<arguments>...]
 
-fn is_valid(passpord: &Passpord) -> bool {
-    let valid_ecl = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
-    passpord.byr >= 1920
-        && passpord.byr <= 2002
-        && passpord.iyr >= 2010
-        && passpord.iyr <= 2020
-        && passpord.eyr >= 2020
-        && passpord.eyr <= 2030
-        && (match passpord.hgt {
-            (val, "cm") => val >= 150 && val <= 193,
-            (val, "in") => val >= 59 && val <= 76,
-            _ => false,
-        })
-        && &passpord.hcl[..1] == "#"
-        && passpord.hcl[1..]
-            .chars()
-            .all(|c| (c.is_digit(10) || c.is_lowercase()) && c.is_digit(16))
-        && valid_ecl.contains(&passpord.ecl)
-        && passpord.pid.len() == 9
-        && passpord.pid.chars().all(|c| c.is_digit(10))
-}
+with the types year <min>-<max>, measure <unit>:<min>-<max>..., color,
+enum <value>..., digits <length>, regex <pattern> and any.
+
+--report lists the missing and invalid fields of every record.";
+
+const OPTIONS: &[Opt] = &[Opt::value("--schema"), Opt::flag("--report")];
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input = read_to_string(args().nth(1).ok_or("No input file")?)?;
-    let passpords: Vec<_> = parse_input(&input).collect();
+    let cli = Cli::from_env_with(USAGE, OPTIONS)?;
+    let input = cli.input(4)?;
+    if cli.format == Format::Json {
+        print_json::<Day4>(4, &input, cli.mode);
+        return Ok(());
+    }
+    let custom: Schema;
+    let schema = match cli.value("--schema") {
+        Some(file) => {
+            custom = fs::read_to_string(file)?
+                .parse()
+                .map_err(|e: Error| e.in_file(file))?;
+            &custom
+        }
+        None => passport_schema(),
+    };
+    let documents = input.parse::<Day4>(cli.mode)?;
+
+    if cli.flag("--report") {
+        for document in &documents {
+            let violations = schema.check(document);
+            let verdict = if violations.is_empty() {
+                "valid"
+            } else {
+                "invalid"
+            };
+            println!("record at line {}: {}", document.line, verdict);
+            for violation in violations {
+                println!("  {}", violation);
+            }
+        }
+    }
 
-    let part1 = passpords.len();
+    let part1 = documents.iter().filter(|d| schema.has_required(d)).count();
     println!("Part1: {}", part1);
 
-    let part2 = passpords.iter().filter(|p| is_valid(p)).count();
+    let part2 = documents.iter().filter(|d| schema.is_valid(d)).count();
     println!("Part2: {}", part2);
     Ok(())
 }
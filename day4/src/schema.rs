@@ -0,0 +1,270 @@
+use crate::Document;
+use aoc_common::{Diagnostic, Error, Result, Source};
+use regex::Regex;
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+/// What the value of a field has to look like.
+#[derive(Debug, Clone)]
+pub enum FieldType {
+    /// Four digits within the range.
+    Year(RangeInclusive<u32>),
+    /// A number followed by one of the units, within the range of that unit.
+    Measure(Vec<(String, RangeInclusive<u32>)>),
+    /// `#` followed by six lowercase hex digits.
+    Color,
+    Enum(Vec<String>),
+    /// Exactly this many digits.
+    Digits(usize),
+    /// The whole value matches the expression.
+    Pattern {
+        pattern: String,
+        regex: Regex,
+    },
+    Any,
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Parses a number of digits only, unlike `str::parse` allowing no sign.
+fn number(s: &str) -> Option<u32> {
+    Some(s).filter(|s| is_digits(s))?.parse().ok()
+}
+
+impl FieldType {
+    pub fn accepts(&self, value: &str) -> bool {
+        match self {
+            FieldType::Year(range) => {
+                value.len() == 4 && number(value).is_some_and(|year| range.contains(&year))
+            }
+            FieldType::Measure(units) => {
+                let split = value
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(value.len());
+                let (amount, unit) = value.split_at(split);
+                number(amount).is_some_and(|amount| {
+                    units
+                        .iter()
+                        .any(|(name, range)| name == unit && range.contains(&amount))
+                })
+            }
+            FieldType::Color => value.strip_prefix('#').is_some_and(|hex| {
+                hex.len() == 6 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'))
+            }),
+            FieldType::Enum(values) => values.iter().any(|v| v == value),
+            FieldType::Digits(len) => value.len() == *len && is_digits(value),
+            FieldType::Pattern { regex, .. } => regex.is_match(value),
+            FieldType::Any => true,
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldType::Year(range) => write!(f, "year from {} to {}", range.start(), range.end()),
+            FieldType::Measure(units) => {
+                let units: Vec<_> = units
+                    .iter()
+                    .map(|(unit, range)| format!("{} to {}{}", range.start(), range.end(), unit))
+                    .collect();
+                write!(f, "measure of {}", units.join(" or "))
+            }
+            FieldType::Color => write!(f, "color #rrggbb"),
+            FieldType::Enum(values) => write!(f, "one of {}", values.join(", ")),
+            FieldType::Digits(len) => write!(f, "{} digits", len),
+            FieldType::Pattern { pattern, .. } => write!(f, "value matching /{}/", pattern),
+            FieldType::Any => write!(f, "any value"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldRule {
+    pub name: String,
+    pub required: bool,
+    pub kind: FieldType,
+}
+
+/// Rules for the fields of a kind of document, read from lines like
+/// `<field> <required|optional> <type> [<arguments>...]`.
+///
+/// The types are `year <min>-<max>`, `measure <unit>:<min>-<max>...`,
+/// `color`, `enum <value>...`, `digits <length>`, `regex <pattern>` and `any`.
+/// Blank lines and lines starting with `#` are skipped, fields without a rule
+/// are allowed.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    pub rules: Vec<FieldRule>,
+}
+
+/// A field missing from a document or with a value breaking its rule.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Violation {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Schema {
+    /// Whether `document` has all the required fields, whatever their values.
+    pub fn has_required(&self, document: &Document) -> bool {
+        let rules = self.rules.iter().filter(|rule| rule.required);
+        rules
+            .into_iter()
+            .all(|rule| document.get(&rule.name).is_some())
+    }
+
+    /// Every missing or invalid field of `document`.
+    pub fn check(&self, document: &Document) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for rule in &self.rules {
+            match document.get(&rule.name) {
+                None if rule.required => violations.push(Violation {
+                    line: document.line,
+                    column: 1,
+                    message: format!("missing {}", rule.name),
+                }),
+                Some(field) if !rule.kind.accepts(&field.value) => violations.push(Violation {
+                    line: field.line,
+                    column: field.column,
+                    message: format!(
+                        "{}: expected {}, found {:?}",
+                        rule.name, rule.kind, field.value
+                    ),
+                }),
+                _ => {}
+            }
+        }
+        violations
+    }
+
+    pub fn is_valid(&self, document: &Document) -> bool {
+        self.check(document).is_empty()
+    }
+}
+
+fn parse_range<'a>(source: &Source<'a>, s: &'a str) -> Result<RangeInclusive<u32>, Diagnostic> {
+    let (min, max) = source.split(s, "-")?;
+    let min = number(min).ok_or_else(|| source.error(min, "number"))?;
+    let max = number(max).ok_or_else(|| source.error(max, "number"))?;
+    if max < min {
+        return Err(source.error(s, format!("range from at most {}", max)));
+    }
+    Ok(min..=max)
+}
+
+fn parse_rule<'a>(source: &Source<'a>, line: &'a str) -> Result<FieldRule> {
+    let end = &line[line.len()..];
+    let mut words = line.split_whitespace();
+    let name = words
+        .next()
+        .ok_or_else(|| source.error(end, "field name"))?;
+    let required = match words.next() {
+        Some("required") => true,
+        Some("optional") => false,
+        word => {
+            return Err(source
+                .error(word.unwrap_or(end), "required or optional")
+                .into())
+        }
+    };
+    let kind = words
+        .next()
+        .ok_or_else(|| source.error(end, "field type"))?;
+    let kind = match kind {
+        "year" => {
+            let range = words.next().ok_or_else(|| source.error(end, "range"))?;
+            FieldType::Year(parse_range(source, range)?)
+        }
+        "measure" => {
+            let units = words
+                .by_ref()
+                .map(|unit| {
+                    let (name, range) = source.split(unit, ":")?;
+                    Ok((name.to_owned(), parse_range(source, range)?))
+                })
+                .collect::<Result<Vec<_>, Diagnostic>>()?;
+            if units.is_empty() {
+                return Err(source.error(end, "unit").into());
+            }
+            FieldType::Measure(units)
+        }
+        "color" => FieldType::Color,
+        "enum" => {
+            let values: Vec<_> = words.by_ref().map(str::to_owned).collect();
+            if values.is_empty() {
+                return Err(source.error(end, "value").into());
+            }
+            FieldType::Enum(values)
+        }
+        "digits" => {
+            let len = words.next().ok_or_else(|| source.error(end, "length"))?;
+            FieldType::Digits(source.parse(len, "length")?)
+        }
+        "regex" => {
+            // the rest of the line, spaces included
+            let offset = kind.as_ptr() as usize - line.as_ptr() as usize + kind.len();
+            let pattern = line[offset..].trim();
+            if pattern.is_empty() {
+                return Err(source.error(end, "pattern").into());
+            }
+            let regex = Regex::new(&format!("^(?:{})$", pattern))
+                .map_err(|_| source.error(pattern, "regular expression"))?;
+            FieldType::Pattern {
+                pattern: pattern.to_owned(),
+                regex,
+            }
+        }
+        "any" => FieldType::Any,
+        _ => {
+            return Err(source
+                .error(kind, "year, measure, color, enum, digits, regex or any")
+                .into())
+        }
+    };
+    let extra = match kind {
+        FieldType::Pattern { .. } => None,
+        _ => words.next(),
+    };
+    if let Some(extra) = extra {
+        return Err(source.error(extra, "end of line").into());
+    }
+    Ok(FieldRule {
+        name: name.to_owned(),
+        required,
+        kind,
+    })
+}
+
+impl FromStr for Schema {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let source = Source::new(s);
+        let mut rules: Vec<FieldRule> = Vec::new();
+        for line in s.lines() {
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let rule = parse_rule(&source, trimmed)?;
+            if rules.iter().any(|other| other.name == rule.name) {
+                return Err(source.error(trimmed, "field without a rule yet").into());
+            }
+            rules.push(rule);
+        }
+        Ok(Schema { rules })
+    }
+}
@@ -0,0 +1,69 @@
+use aoc_common::{Error, Mode, Result, Solution};
+
+pub fn parse_input<'a>(input: &'a str) -> impl Iterator<Item = u16> + 'a {
+    input.trim().split('\n').map(|row| {
+        row.chars()
+            .map(|c| match c {
+                'B' | 'R' => 1,
+                _ => 0,
+            })
+            .fold(0, |acc, b| acc * 2 + b)
+    })
+}
+
+/// Returns the highest seat id and the id of the only missing seat.
+pub fn solve(seats: &[u16]) -> (u16, u16) {
+    let (min, max, prod) = seats
+        .iter()
+        .fold((u16::MAX, u16::MIN, 0), |(min, max, prod), &v| {
+            (min.min(v), max.max(v), prod ^ v)
+        });
+
+    let my_seat = (min..=max).fold(prod, std::ops::BitXor::bitxor);
+    (max, my_seat)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<u16>;
+
+    fn parse(input: &str, _mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input).collect())
+    }
+
+    fn part1(seats: &Self::Input) -> Result<String> {
+        let max = seats.iter().max().ok_or(Error::NoSolution)?;
+        Ok(max.to_string())
+    }
+
+    fn part2(seats: &Self::Input) -> Result<String> {
+        Ok(solve(seats).1.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    #[test]
+    fn seat_ids() {
+        let seats: Vec<_> =
+            parse_input("FBFBBFFRLR\nBFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n").collect();
+        assert_eq!(seats, vec![357, 567, 119, 820]);
+    }
+
+    #[test]
+    fn missing_seat() {
+        let seats = [3, 4, 6, 7];
+        assert_eq!(solve(&seats), (7, 5));
+        assert_eq!(Day5::part2(&seats.to_vec()).unwrap(), "5");
+    }
+
+    #[test]
+    fn highest_seat() {
+        let input = "FBFBBFFRLR\nBFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL\n";
+        assert_eq!(Day5::solve(input, Part::One, Mode::Strict).unwrap(), "820");
+    }
+}
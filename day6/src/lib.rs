@@ -0,0 +1,73 @@
+use aoc_common::{sections, Mode, Result, Solution};
+use std::collections::HashSet;
+
+pub fn part1(input: &str) -> usize {
+    sections(input)
+        .map(|group| {
+            group
+                .chars()
+                .filter(|c| c.is_alphabetic())
+                .collect::<HashSet<_>>()
+                .len()
+        })
+        .sum()
+}
+
+pub fn part2(input: &str) -> usize {
+    sections(input)
+        .map(|group| {
+            group
+                .lines()
+                .map(|line| line.trim().chars().collect::<HashSet<_>>())
+                .fold(None, |acc, ans| match acc {
+                    None => Some(ans),
+                    Some(acc) => Some(acc.intersection(&ans).cloned().collect()),
+                })
+                .map(|v| v.len())
+                .unwrap_or(0)
+        })
+        .sum()
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = String;
+
+    fn parse(input: &str, _mode: Mode) -> Result<Self::Input> {
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Input) -> Result<String> {
+        Ok(part1(input).to_string())
+    }
+
+    fn part2(input: &Self::Input) -> Result<String> {
+        Ok(part2(input).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb\n";
+
+    #[test]
+    fn anyone_answered() {
+        assert_eq!(part1(EXAMPLE), 11);
+        assert_eq!(part1("abcx\nabcy\nabcz"), 6);
+    }
+
+    #[test]
+    fn everyone_answered() {
+        assert_eq!(part2(EXAMPLE), 6);
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(Day6::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(), "11");
+        assert_eq!(Day6::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(), "6");
+    }
+}
@@ -1,37 +1,13 @@
-use std::{collections::HashSet, env::args, fs::read_to_string};
-
-fn part1(input: &str) -> usize {
-    input
-        .split("\n\n")
-        .map(|group| {
-            group
-                .chars()
-                .filter(|c| c.is_alphabetic())
-                .collect::<HashSet<_>>()
-                .len()
-        })
-        .sum()
-}
-
-fn part2(input: &str) -> usize {
-    input
-        .split("\n\n")
-        .map(|group| {
-            group
-                .lines()
-                .map(|line| line.trim().chars().collect::<HashSet<_>>())
-                .fold(None, |acc, ans| match acc {
-                    None => Some(ans),
-                    Some(acc) => Some(acc.intersection(&ans).cloned().collect()),
-                })
-                .map(|v| v.len())
-                .unwrap_or(0)
-        })
-        .sum()
-}
+use aoc_common::{print_json, Cli, Format};
+use day6::{part1, part2, Day6};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input = read_to_string(args().nth(1).ok_or("No input file")?)?;
+    let cli = Cli::from_env()?;
+    let input = cli.input(6)?;
+    if cli.format == Format::Json {
+        print_json::<Day6>(6, &input, cli.mode);
+        return Ok(());
+    }
 
     let total1 = part1(&input);
     let total2 = part2(&input);
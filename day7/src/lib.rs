@@ -0,0 +1,156 @@
+use aoc_common::{collect, Diagnostic, Mode, Result, Solution, Source};
+use std::collections::VecDeque;
+
+/// Bag color with the counts and colors of bags it contains.
+pub type Bag = (String, Vec<(i32, String)>);
+
+fn parse_content<'a>(source: &Source<'a>, content: &'a str) -> Result<(i32, String), Diagnostic> {
+    let (n, color) = source.split(content, " ")?;
+    let color = color
+        .strip_suffix(" bags")
+        .or_else(|| color.strip_suffix(" bag"))
+        .ok_or_else(|| source.error(&color[color.len()..], "\" bag\" or \" bags\""))?;
+    Ok((source.parse(n, "number")?, color.to_owned()))
+}
+
+fn parse_rule<'a>(source: &Source<'a>, line: &'a str) -> Result<Bag, Diagnostic> {
+    let line = line.trim().trim_end_matches('.');
+    let (bag, rest) = source.split(line, " bags contain ")?;
+    let contents = match rest {
+        "no other bags" => Vec::new(),
+        rest => rest
+            .split(", ")
+            .map(|content| parse_content(source, content))
+            .collect::<Result<_, _>>()?,
+    };
+    Ok((bag.to_owned(), contents))
+}
+
+pub fn parse_input(input: &str, mode: Mode) -> Result<Vec<Bag>, Diagnostic> {
+    let source = Source::new(input);
+    collect(input.lines().map(|line| parse_rule(&source, line)), mode)
+}
+
+pub fn part1(my_bag: &str, bags: &[Bag]) -> usize {
+    let mut queue = VecDeque::new();
+    let mut result = std::collections::HashSet::new();
+
+    queue.push_back(my_bag);
+    while let Some(current) = queue.pop_front() {
+        for (bag, inner) in bags.iter() {
+            if inner.iter().any(|(_n, c)| c == current) && !result.contains(bag) {
+                result.insert(bag);
+                queue.push_back(bag);
+            }
+        }
+    }
+
+    result.len()
+}
+
+pub fn part2(my_bag: &str, bags: &[Bag]) -> i32 {
+    let mut queue = VecDeque::new();
+    let mut result = 0;
+
+    queue.push_back((1, my_bag));
+    while let Some((cnt, current)) = queue.pop_front() {
+        if let Some((_bag, inner)) = bags.iter().find(|(bag, _)| bag == current) {
+            for (count, color) in inner {
+                result += cnt * count;
+                queue.push_back((cnt * count, color));
+            }
+        }
+    }
+
+    result
+}
+
+pub const MY_BAG: &str = "shiny gold";
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<Bag>;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input, mode)?)
+    }
+
+    fn part1(bags: &Self::Input) -> Result<String> {
+        Ok(part1(MY_BAG, bags).to_string())
+    }
+
+    fn part2(bags: &Self::Input) -> Result<String> {
+        Ok(part2(MY_BAG, bags).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "\
+light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.
+";
+
+    const NESTED: &str = "\
+shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.
+";
+
+    #[test]
+    fn parse_rules() {
+        let bags = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        assert_eq!(bags.len(), 9);
+        assert_eq!(
+            bags[0],
+            (
+                "light red".to_owned(),
+                vec![
+                    (1, "bright white".to_owned()),
+                    (2, "muted yellow".to_owned())
+                ]
+            )
+        );
+        assert_eq!(bags[7], ("faded blue".to_owned(), Vec::new()));
+    }
+
+    #[test]
+    fn malformed_rules() {
+        let input =
+            "faded blue bags contain no other bags.\nshiny gold bags contain two dark red bags.\n";
+        assert_eq!(parse_input(input, Mode::Lenient).unwrap().len(), 1);
+        let err = parse_input(input, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 25));
+        assert_eq!(err.expected, "number");
+
+        let err = parse_input("shiny gold bags hold 2 dark red bags.", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 37));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(Day7::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(), "4");
+        assert_eq!(Day7::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(), "32");
+    }
+
+    #[test]
+    fn nested_bags() {
+        let bags = parse_input(NESTED, Mode::Strict).unwrap();
+        assert_eq!(part2(MY_BAG, &bags), 126);
+    }
+}
@@ -0,0 +1,141 @@
+use aoc_common::{collect, Diagnostic, Error, Mode, Result, Solution, Source};
+
+#[derive(Debug, Clone)]
+pub enum Ins {
+    Acc(i32),
+    Jmp(i32),
+    Nop(i32),
+}
+
+fn parse_ins<'a>(source: &Source<'a>, line: &'a str) -> Result<Ins, Diagnostic> {
+    let (ins, num) = source.split(line, " ")?;
+    let ins = match ins {
+        "acc" => Ins::Acc,
+        "jmp" => Ins::Jmp,
+        "nop" => Ins::Nop,
+        _ => return Err(source.error(ins, "\"acc\", \"jmp\" or \"nop\"")),
+    };
+    Ok(ins(source.parse(num, "signed number")?))
+}
+
+pub fn parse_input(input: &str, mode: Mode) -> Result<Vec<Ins>, Diagnostic> {
+    let source = Source::new(input);
+    collect(input.lines().map(|l| parse_ins(&source, l)), mode)
+}
+
+pub fn eval(instructions: &[Ins]) -> (bool, i32) {
+    let mut counter = 0;
+    let mut idx = 0;
+    let mut visited = vec![false; instructions.len()];
+
+    loop {
+        if idx >= instructions.len() {
+            break;
+        }
+        if visited[idx] {
+            return (false, counter);
+        }
+        visited[idx] = true;
+
+        match &instructions[idx] {
+            Ins::Acc(val) => counter += val,
+            Ins::Jmp(offset) => {
+                idx = (idx as i32 + *offset) as usize;
+                continue;
+            }
+            Ins::Nop(_) => (),
+        }
+
+        idx += 1;
+    }
+
+    (true, counter)
+}
+
+/// Finds the single `jmp`/`nop` swap that makes the program terminate.
+pub fn repair(instructions: &[Ins]) -> Option<i32> {
+    for idx in 0..instructions.len() {
+        let replacement = match instructions[idx] {
+            Ins::Nop(v) => Ins::Jmp(v),
+            Ins::Jmp(v) => Ins::Nop(v),
+            _ => continue,
+        };
+
+        let mut new_instructions = instructions.to_vec();
+        new_instructions[idx] = replacement;
+
+        if let (true, val) = eval(new_instructions.as_slice()) {
+            return Some(val);
+        }
+    }
+    None
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Ins>;
+
+    fn parse(input: &str, mode: Mode) -> Result<Self::Input> {
+        Ok(parse_input(input, mode)?)
+    }
+
+    fn part1(instructions: &Self::Input) -> Result<String> {
+        Ok(eval(instructions).1.to_string())
+    }
+
+    fn part2(instructions: &Self::Input) -> Result<String> {
+        Ok(repair(instructions).ok_or(Error::NoSolution)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Part;
+
+    const EXAMPLE: &str = "\
+nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6
+";
+
+    #[test]
+    fn infinite_loop() {
+        let instructions = parse_input(EXAMPLE, Mode::Strict).unwrap();
+        assert_eq!(eval(&instructions), (false, 5));
+    }
+
+    #[test]
+    fn terminates() {
+        let instructions = parse_input("nop +0\nacc +2\njmp +1\nacc -1\n", Mode::Strict).unwrap();
+        assert_eq!(eval(&instructions), (true, 1));
+    }
+
+    #[test]
+    fn trailing_carriage_return() {
+        let input = "nop +0\nacc +2\r\r\njmp +1\nacc -1\n";
+        assert_eq!(parse_input(input, Mode::Lenient).unwrap().len(), 3);
+        let err = parse_input(input, Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 5: expected signed number, found \"+2\\r\\r\""
+        );
+
+        let err = parse_input("nop +0\nadd +1\n", Mode::Strict).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(Day8::solve(EXAMPLE, Part::One, Mode::Strict).unwrap(), "5");
+        assert_eq!(Day8::solve(EXAMPLE, Part::Two, Mode::Strict).unwrap(), "8");
+    }
+}
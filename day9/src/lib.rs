@@ -0,0 +1,97 @@
+use aoc_common::{parse_lines, Error, Mode, Result, Solution};
+use std::{cmp::Ordering, collections::HashSet};
+
+pub const WINDOW: usize = 25;
+
+pub fn find_subsequence(numbers: &[i64], target: i64) -> Option<(usize, usize)> {
+    let (mut set_start, mut set_end) = (0, 0);
+    while set_end < numbers.len() {
+        if set_end - set_start < 2 {
+            set_end += 1;
+            continue;
+        }
+
+        let current_sum: i64 = numbers[set_start..=set_end].iter().sum();
+        match target.cmp(&current_sum) {
+            Ordering::Equal => return Some((set_start, set_end)),
+            Ordering::Greater => set_end += 1,
+            Ordering::Less => set_start += 1,
+        }
+    }
+
+    None
+}
+
+/// Finds the first number which is not a sum of two of the `window` numbers
+/// before it and the sum of the smallest and largest number of the contiguous
+/// range adding up to it.
+pub fn solve(numbers: &[i64], window: usize) -> Option<(i64, i64)> {
+    for chunk in numbers.windows(window + 1) {
+        let prev = &chunk[..window];
+        let target = chunk[window];
+
+        let prev_set: HashSet<_> = prev.iter().collect(); // TODO duplicates ???
+
+        let mut found = false;
+        for n in prev {
+            if prev_set.contains(&(target - n)) {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            if let Some((s, e)) = find_subsequence(numbers, target) {
+                let range = &numbers[s..=e];
+                let min = range.iter().min().unwrap();
+                let max = range.iter().max().unwrap();
+                return Some((target, min + max));
+            }
+        }
+    }
+    None
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<i64>;
+
+    fn parse(input: &str, _mode: Mode) -> Result<Self::Input> {
+        Ok(parse_lines(input)?)
+    }
+
+    fn part1(numbers: &Self::Input) -> Result<String> {
+        let (target, _) = solve(numbers, WINDOW).ok_or(Error::NoSolution)?;
+        Ok(target.to_string())
+    }
+
+    fn part2(numbers: &Self::Input) -> Result<String> {
+        let (_, weakness) = solve(numbers, WINDOW).ok_or(Error::NoSolution)?;
+        Ok(weakness.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: [i64; 20] = [
+        35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309, 576,
+    ];
+
+    #[test]
+    fn contiguous_range() {
+        assert_eq!(find_subsequence(&EXAMPLE, 127), Some((2, 5)));
+    }
+
+    #[test]
+    fn example_answers() {
+        assert_eq!(solve(&EXAMPLE, 5), Some((127, 62)));
+    }
+
+    #[test]
+    fn every_number_valid() {
+        let numbers: Vec<_> = (1..=26).collect();
+        assert_eq!(solve(&numbers, 25), None);
+    }
+}